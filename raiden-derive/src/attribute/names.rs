@@ -40,6 +40,18 @@ pub fn expand_attr_names(
         }
     });
 
+    let policy_arms = fields.named.iter().map(|f| {
+        let ident = &f.ident.clone().unwrap();
+        let renamed = crate::finder::find_rename_value(&f.attrs);
+        let basename = create_renamed(ident.to_string(), renamed, rename_all_type);
+        let name = exclude_raw_ident(&basename).to_case(Case::Pascal);
+        let name = format_ident!("{}", name);
+        let policy = crate::helpers::empty_value_policy(&f.attrs);
+        quote! {
+            #attr_enum_name::#name => #policy
+        }
+    });
+
     let getters = fields.named.iter().map(|f| {
         let ident = &f.ident.clone().unwrap();
         let renamed = crate::finder::find_rename_value(&f.attrs);
@@ -75,6 +87,14 @@ pub fn expand_attr_names(
                     )*
                 }
             }
+
+            fn empty_value_policy(self) -> ::raiden::EmptyValuePolicy {
+                match self {
+                    #(
+                        #policy_arms,
+                    )*
+                }
+            }
         }
 
         // attr name getter
@@ -87,7 +107,7 @@ pub fn expand_attr_names(
     }
 }
 
-fn exclude_raw_ident(ident: &str) -> String {
+pub(crate) fn exclude_raw_ident(ident: &str) -> String {
     if &ident[0..2] == "r#" {
         ident[2..].to_owned()
     } else {
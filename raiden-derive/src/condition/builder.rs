@@ -3,11 +3,22 @@ use quote::*;
 pub fn expand_condition_builder(
     attr_enum_name: &proc_macro2::Ident,
     struct_name: &proc_macro2::Ident,
-    _fields: &syn::FieldsNamed,
+    fields: &syn::FieldsNamed,
+    rename_all_type: crate::rename::RenameAllType,
 ) -> proc_macro2::TokenStream {
     let condition_name = format_ident!("{}Condition", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
     let wait_attr_op_name = format_ident!("{}LeftAttrAndWaitOp", struct_name);
+    let condition_fields_name = format_ident!("{}ConditionFields", struct_name);
+    let accessors = crate::helpers::typed_field_accessors(attr_enum_name, fields, rename_all_type)
+        .into_iter()
+        .map(|(func_name, constructor, ty)| {
+            quote! {
+                pub fn #func_name(self) -> ::raiden::TypedCondition<#condition_token_name, #ty> {
+                    ::raiden::TypedCondition::#constructor
+                }
+            }
+        });
 
     quote! {
 
@@ -26,6 +37,25 @@ pub fn expand_condition_builder(
                     not: false,
                 }
             }
+
+            pub fn cond() -> #condition_fields_name {
+                #condition_fields_name
+            }
+
+            // Types an already built or parsed tree, e.g. from `raiden::expr::parse_condition`, for this table.
+            pub fn condition_from(expr: ::raiden::condition::ConditionExpr) -> ::raiden::ConditionFromExpr<#condition_token_name> {
+                ::raiden::ConditionFromExpr {
+                    expr,
+                    _token: std::marker::PhantomData,
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy)]
+        pub struct #condition_fields_name;
+
+        impl #condition_fields_name {
+            #(#accessors)*
         }
 
         impl #condition_name {
@@ -38,6 +68,7 @@ pub fn expand_condition_builder(
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
@@ -46,6 +77,7 @@ pub fn expand_condition_builder(
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
@@ -54,6 +86,7 @@ pub fn expand_condition_builder(
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
@@ -62,6 +95,7 @@ pub fn expand_condition_builder(
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
@@ -71,6 +105,7 @@ pub fn expand_condition_builder(
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
@@ -78,45 +113,43 @@ pub fn expand_condition_builder(
             pub fn attr(self, field: #attr_enum_name) -> #wait_attr_op_name {
                 #wait_attr_op_name {
                     not: self.not,
-                    attr_or_placeholder: ::raiden::AttrOrPlaceholder::Attr(field.into_attr_name()),
-                    attr_value: None,
+                    attr_or_value: ::raiden::AttrOrValue::Attr(field.into_attr_name()),
                 }
             }
 
             pub fn value(self, value: impl ::raiden::IntoAttribute) -> #wait_attr_op_name {
-                let placeholder = format!("value{}", ::raiden::generate_value_id());
                 #wait_attr_op_name {
                     not: self.not,
-                    attr_or_placeholder: ::raiden::AttrOrPlaceholder::Placeholder(placeholder),
-                    attr_value: Some(value.into_attr()),
+                    attr_or_value: ::raiden::AttrOrValue::Value(value.into_attr()),
                 }
             }
         }
 
         pub struct #wait_attr_op_name {
             not: bool,
-            attr_or_placeholder: ::raiden::AttrOrPlaceholder,
-            attr_value: Option<::raiden::AttributeValue>
+            attr_or_value: ::raiden::AttrOrValue,
         }
 
         impl #wait_attr_op_name {
             pub fn eq_attr(self, attr: #attr_enum_name) -> ::raiden::ConditionFilledOrWaitOperator<#condition_token_name>  {
-                let attr = ::raiden::AttrOrPlaceholder::Attr(attr.into_attr_name());
-                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_placeholder, self.attr_value, attr, None));
+                let attr = ::raiden::AttrOrValue::Attr(attr.into_attr_name());
+                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_value, attr));
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
 
             }
 
             pub fn eq_value(self, value: impl ::raiden::IntoAttribute) -> ::raiden::ConditionFilledOrWaitOperator<#condition_token_name>  {
-                let placeholder = ::raiden::AttrOrPlaceholder::Placeholder(format!("value{}", ::raiden::generate_value_id()));
-                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_placeholder, self.attr_value, placeholder, Some(value.into_attr())));
+                let value = ::raiden::AttrOrValue::Value(value.into_attr());
+                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_value, value));
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
+                    error: None,
                     _token: std::marker::PhantomData,
                 }
             }
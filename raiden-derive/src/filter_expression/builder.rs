@@ -3,8 +3,21 @@ use quote::*;
 pub fn expand_filter_expression_builder(
     attr_enum_name: &proc_macro2::Ident,
     struct_name: &proc_macro2::Ident,
+    fields: &syn::FieldsNamed,
+    rename_all_type: crate::rename::RenameAllType,
 ) -> proc_macro2::TokenStream {
     let filter_expression_token_name = format_ident!("{}FilterExpressionToken", struct_name);
+    let filter_fields_name = format_ident!("{}FilterExpressionFields", struct_name);
+    let accessors =
+        crate::helpers::typed_field_accessors(attr_enum_name, fields, rename_all_type)
+            .into_iter()
+            .map(|(func_name, constructor, ty)| {
+                quote! {
+                    pub fn #func_name(self) -> ::raiden::TypedFilterExpression<#filter_expression_token_name, #ty> {
+                        ::raiden::TypedFilterExpression::#constructor
+                    }
+                }
+            });
     quote! {
 
         pub struct #filter_expression_token_name;
@@ -18,6 +31,25 @@ pub fn expand_filter_expression_builder(
                     _token: std::marker::PhantomData,
                 }
             }
+
+            pub fn filter() -> #filter_fields_name {
+                #filter_fields_name
+            }
+
+            // Types an already built or parsed tree, e.g. from `raiden::expr::parse_filter`, for this table.
+            pub fn filter_from(expr: ::raiden::filter_expression::FilterExpr) -> ::raiden::FilterExpressionFromExpr<#filter_expression_token_name> {
+                ::raiden::FilterExpressionFromExpr {
+                    expr,
+                    _token: std::marker::PhantomData,
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy)]
+        pub struct #filter_fields_name;
+
+        impl #filter_fields_name {
+            #(#accessors)*
         }
     }
 }
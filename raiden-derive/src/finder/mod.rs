@@ -75,6 +75,19 @@ pub(crate) fn find_rename_value(attrs: &[syn::Attribute]) -> Option<String> {
     None
 }
 
+pub(crate) fn find_eq_string_attr(attrs: &[syn::Attribute], name: &str) -> Option<String> {
+    for attr in attrs {
+        if let Some(lit) = find_eq_string_from(attr, name) {
+            return Some(lit);
+        }
+    }
+    None
+}
+
+pub(crate) fn find_format(attrs: &[syn::Attribute]) -> Option<String> {
+    find_eq_string_attr(attrs, "format")
+}
+
 pub(crate) fn include_unary_attr(attrs: &[syn::Attribute], name: &str) -> bool {
     !attrs.is_empty()
         && attrs.iter().any(|attr| {
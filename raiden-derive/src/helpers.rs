@@ -53,3 +53,226 @@ pub fn is_reserved(v: &str) -> bool {
             | "try"
     )
 }
+
+// Returns `T` for `Option<T>`, otherwise the type itself.
+pub fn unwrap_option(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty {
+        if let Some(segment) = path.segments.last() {
+            if segment.ident == "Option" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        return inner;
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
+
+// Accessor name, constructor call and value type of a field,
+// used to emit typed per-field expression builders such as `User::filter().age()`.
+// The constructor is `new(..)`, or `with_encoder(..)` when the field isn't stored with `IntoAttribute`.
+pub fn typed_field_accessor(
+    attr_enum_name: &proc_macro2::Ident,
+    basename: &str,
+    attrs: &[syn::Attribute],
+    ty: &syn::Type,
+) -> (proc_macro2::Ident, proc_macro2::TokenStream, syn::Type) {
+    use convert_case::{Case, Casing};
+    use quote::*;
+
+    let func_name = basename.to_case(Case::Snake);
+    let func_name = if is_reserved(&func_name) {
+        format_ident!("r#{}", func_name)
+    } else {
+        format_ident!("{}", func_name)
+    };
+    let name = crate::attribute::exclude_raw_ident(basename).to_case(Case::Pascal);
+    let name = format_ident!("{}", name);
+    let attr = quote! { #attr_enum_name::#name.into_attr_name() };
+    let value_ty = unwrap_option(ty).clone();
+    let constructor = if has_custom_conversion(attrs) {
+        let value = if crate::finder::is_option(ty) {
+            quote! { Some(value) }
+        } else {
+            quote! { value }
+        };
+        let encoded = try_into_attr(attrs, ty, value);
+        quote! {
+            with_encoder(#attr, |value: #value_ty| -> Result<::raiden::AttributeValue, ::raiden::ConversionError> { #encoded })
+        }
+    } else {
+        quote! { new(#attr) }
+    };
+    (func_name, constructor, value_ty)
+}
+
+pub fn typed_field_accessors(
+    attr_enum_name: &proc_macro2::Ident,
+    fields: &syn::FieldsNamed,
+    rename_all_type: crate::rename::RenameAllType,
+) -> Vec<(proc_macro2::Ident, proc_macro2::TokenStream, syn::Type)> {
+    fields
+        .named
+        .iter()
+        .map(|f| {
+            let ident = &f.ident.clone().unwrap();
+            let renamed = crate::finder::find_rename_value(&f.attrs);
+            let basename =
+                crate::rename::create_renamed(ident.to_string(), renamed, rename_all_type);
+            typed_field_accessor(attr_enum_name, &basename, &f.attrs, &f.ty)
+        })
+        .collect()
+}
+
+// Such a field is converted by its converter, format or serde instead of `IntoAttribute`.
+fn has_custom_conversion(attrs: &[syn::Attribute]) -> bool {
+    find_converter(attrs, "into_with", "into_attr").is_some()
+        || crate::finder::find_format(attrs).is_some()
+        || crate::finder::include_unary_attr(attrs, "serde")
+}
+
+// Function set with `#[raiden(into_with = "path")]` / `#[raiden(from_with = "path")]`,
+// or `function` of the module set with `#[raiden(with = "module")]`.
+fn find_converter(attrs: &[syn::Attribute], name: &str, function: &str) -> Option<syn::Path> {
+    let path = crate::finder::find_eq_string_attr(attrs, name).or_else(|| {
+        crate::finder::find_eq_string_attr(attrs, "with")
+            .map(|module| format!("{}::{}", module, function))
+    })?;
+    match syn::parse_str(&path) {
+        Ok(path) => Some(path),
+        Err(_) => panic!("{} is not a valid path", path),
+    }
+}
+
+// Function set with `#[raiden(default = "path")]`.
+pub fn find_default_fn(attrs: &[syn::Attribute]) -> Option<syn::Path> {
+    let path = crate::finder::find_eq_string_attr(attrs, "default")?;
+    match syn::parse_str(&path) {
+        Ok(path) => Some(path),
+        Err(_) => panic!("{} is not a valid path", path),
+    }
+}
+
+// Policy selected with `#[raiden(empty_value = "...")]`, `EmptyValuePolicy::Null` by default.
+pub fn empty_value_policy(attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
+    use quote::*;
+
+    match crate::finder::find_eq_string_attr(attrs, "empty_value").as_deref() {
+        None | Some("null") => quote! { ::raiden::EmptyValuePolicy::Null },
+        Some("omit") => quote! { ::raiden::EmptyValuePolicy::Omit },
+        Some("empty") => quote! { ::raiden::EmptyValuePolicy::Empty },
+        Some(policy) => panic!("{} is not supported empty value policy", policy),
+    }
+}
+
+// Format selected with `#[raiden(format = "...")]`.
+pub fn find_format(attrs: &[syn::Attribute]) -> Option<proc_macro2::TokenStream> {
+    use quote::*;
+
+    crate::finder::find_format(attrs).map(|format| match format.as_str() {
+        "rfc3339" => quote! { ::raiden::format::Rfc3339 },
+        "epoch_millis" => quote! { ::raiden::format::EpochMillis },
+        _ => panic!("{} is not supported format", format),
+    })
+}
+
+// A field converts with at most one of `with`/`into_with`/`from_with`, `format` and `serde`,
+// so that it is read back the way it was written.
+fn check_single_conversion(attrs: &[syn::Attribute]) {
+    let converter = ["with", "into_with", "from_with"]
+        .iter()
+        .any(|name| crate::finder::find_eq_string_attr(attrs, name).is_some());
+    let format = crate::finder::find_format(attrs).is_some();
+    let serde = crate::finder::include_unary_attr(attrs, "serde");
+    if [converter, format, serde]
+        .iter()
+        .filter(|set| **set)
+        .count()
+        > 1
+    {
+        panic!("with, format and serde can't be combined on one field");
+    }
+}
+
+// `value` converted with the field's converter, format, serde if selected or `IntoAttribute`,
+// as a `Result<AttributeValue, ConversionError>` since serializing can fail.
+pub fn try_into_attr(
+    attrs: &[syn::Attribute],
+    ty: &syn::Type,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use quote::*;
+
+    check_single_conversion(attrs);
+    if let Some(converter) = find_converter(attrs, "into_with", "into_attr") {
+        return quote! { Ok::<::raiden::AttributeValue, ::raiden::ConversionError>(#converter(#value)) };
+    }
+    if let Some(format) = find_format(attrs) {
+        return quote! { <#format as ::raiden::format::AttributeFormat<#ty>>::into_attr(#value) };
+    }
+    if crate::finder::include_unary_attr(attrs, "serde") {
+        return quote! { ::raiden::serde_attr::to_attr::<#ty>(&#value) };
+    }
+    quote! { Ok::<::raiden::AttributeValue, ::raiden::ConversionError>(#value.into_attr()) }
+}
+
+// Converts `value` like `try_into_attr`, keeping a failure in `slot`, a `&mut Option<DeferredConversionError>`
+// the builder reports on `run`.
+pub fn collect_into_attr(
+    attrs: &[syn::Attribute],
+    ty: &syn::Type,
+    value: proc_macro2::TokenStream,
+    attr_name: &str,
+    slot: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use quote::*;
+
+    let converted = try_into_attr(attrs, ty, value);
+    quote! { ::raiden::DeferredConversionError::collect(#slot, #attr_name, #converted) }
+}
+
+// `FromAttribute::from_attr(value)`, converted with the field's converter, format or through serde if selected.
+pub fn from_attr(
+    attrs: &[syn::Attribute],
+    ty: &syn::Type,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use quote::*;
+
+    check_single_conversion(attrs);
+    if let Some(converter) = find_converter(attrs, "from_with", "from_attr") {
+        return quote! { { let converted: Result<#ty, ::raiden::ConversionError> = #converter(#value); converted } };
+    }
+    if let Some(format) = find_format(attrs) {
+        return quote! { <#format as ::raiden::format::AttributeFormat<#ty>>::from_attr(#value) };
+    }
+    if crate::finder::include_unary_attr(attrs, "serde") {
+        return quote! { ::raiden::serde_attr::from_attr::<#ty>(#value) };
+    }
+    quote! { ::raiden::FromAttribute::from_attr(#value) }
+}
+
+// Converts a partition or sort key argument, honoring the key field's conversion attributes.
+pub fn key_into_attr(
+    fields: &syn::FieldsNamed,
+    is_sort_key: bool,
+    value: proc_macro2::TokenStream,
+    slot: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    use quote::*;
+
+    let field = if is_sort_key {
+        crate::finder::find_sort_key_field(fields)
+    } else {
+        crate::finder::find_partition_key_field(fields)
+    };
+    match field {
+        Some(field) => {
+            let attr_name = field.ident.as_ref().unwrap().to_string();
+            collect_into_attr(&field.attrs, &field.ty, value, &attr_name, slot)
+        }
+        None => quote! { #value.into_attr() },
+    }
+}
@@ -3,13 +3,45 @@ use quote::*;
 pub fn expand_key_condition_builder(
     attr_enum_name: &proc_macro2::Ident,
     struct_name: &proc_macro2::Ident,
+    partition_key: &(proc_macro2::Ident, syn::Type),
+    sort_key: &Option<(proc_macro2::Ident, syn::Type)>,
+    fields: &syn::FieldsNamed,
 ) -> proc_macro2::TokenStream {
     let key_condition_token_name = format_ident!("{}KeyConditionToken", struct_name);
+    let key_condition_fields_name = format_ident!("{}KeyConditionFields", struct_name);
+
+    // Only the table's keys get accessors, so `keys()` can't build a condition DynamoDB would reject.
+    let key_accessor = |field: Option<syn::Field>,
+                        (ident, ty): &(proc_macro2::Ident, syn::Type)| {
+        let attrs = field.map(|field| field.attrs).unwrap_or_default();
+        crate::helpers::typed_field_accessor(attr_enum_name, &ident.to_string(), &attrs, ty)
+    };
+    let partition_key_accessor = {
+        let (func_name, constructor, ty) = key_accessor(
+            crate::finder::find_partition_key_field(fields),
+            partition_key,
+        );
+        quote! {
+            pub fn #func_name(self) -> ::raiden::PartitionKeyCondition<#key_condition_token_name, #ty> {
+                ::raiden::PartitionKeyCondition::#constructor
+            }
+        }
+    };
+    let sort_key_accessor = sort_key.as_ref().map(|sort_key| {
+        let (func_name, constructor, ty) =
+            key_accessor(crate::finder::find_sort_key_field(fields), sort_key);
+        quote! {
+            pub fn #func_name(self) -> ::raiden::SortKeyCondition<#key_condition_token_name, #ty> {
+                ::raiden::SortKeyCondition::#constructor
+            }
+        }
+    });
     quote! {
 
         pub struct #key_condition_token_name;
 
         impl #struct_name {
+            // Accepts any attribute, e.g. for secondary index keys. Use `keys()` for the table's own keys.
             pub fn key_condition(attr: #attr_enum_name) -> ::raiden::KeyCondition<#key_condition_token_name> {
                 let attr = attr.into_attr_name();
                 ::raiden::KeyCondition {
@@ -17,6 +49,27 @@ pub fn expand_key_condition_builder(
                     _token: std::marker::PhantomData,
                 }
             }
+
+            pub fn keys() -> #key_condition_fields_name {
+                #key_condition_fields_name
+            }
+
+            // Types an already built or parsed tree, e.g. from `raiden::expr::parse_key_condition`, for this table.
+            pub fn key_condition_from(expr: ::raiden::key_condition::KeyConditionExpr) -> ::raiden::KeyConditionFromExpr<#key_condition_token_name> {
+                ::raiden::KeyConditionFromExpr {
+                    expr,
+                    _token: std::marker::PhantomData,
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, Copy)]
+        pub struct #key_condition_fields_name;
+
+        impl #key_condition_fields_name {
+            #partition_key_accessor
+
+            #sort_key_accessor
         }
     }
 }
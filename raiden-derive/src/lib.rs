@@ -12,6 +12,7 @@ mod key;
 mod key_condition;
 mod ops;
 mod rename;
+mod transparent;
 
 use crate::rename::*;
 use std::str::FromStr;
@@ -41,7 +42,7 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         rename::RenameAllType::None
     };
 
-    let fields = match input.data {
+    let mut fields = match input.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(n),
             ..
@@ -49,6 +50,17 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         _ => unimplemented!(),
     };
 
+    // A struct level `empty_value` applies to every field without its own.
+    if let Some(policy) = finder::find_eq_string_attr(&attrs, "empty_value") {
+        for field in fields.named.iter_mut() {
+            if finder::find_eq_string_attr(&field.attrs, "empty_value").is_none() {
+                field
+                    .attrs
+                    .push(syn::parse_quote! { #[raiden(empty_value = #policy)] });
+            }
+        }
+    }
+
     let partition_key = key::fetch_partition_key(&fields, rename_all_type);
     let sort_key = key::fetch_sort_key(&fields, rename_all_type);
 
@@ -99,21 +111,34 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         rename_all_type,
     );
 
-    let delete_item = ops::expand_delete_item(&partition_key, &sort_key, &struct_name);
+    let delete_item = ops::expand_delete_item(&partition_key, &sort_key, &struct_name, &fields);
 
-    let batch_delete = ops::expand_batch_delete(&partition_key, &sort_key, &struct_name);
+    let batch_delete = ops::expand_batch_delete(&partition_key, &sort_key, &struct_name, &fields);
 
     let attr_names =
         attribute::expand_attr_names(&attr_enum_name, &fields, rename_all_type, &struct_name);
 
-    let condition_builder =
-        condition::expand_condition_builder(&attr_enum_name, &struct_name, &fields);
+    let condition_builder = condition::expand_condition_builder(
+        &attr_enum_name,
+        &struct_name,
+        &fields,
+        rename_all_type,
+    );
 
-    let key_condition_builder =
-        key_condition::expand_key_condition_builder(&attr_enum_name, &struct_name);
+    let key_condition_builder = key_condition::expand_key_condition_builder(
+        &attr_enum_name,
+        &struct_name,
+        &partition_key,
+        &sort_key,
+        &fields,
+    );
 
-    let filter_expression_builder =
-        filter_expression::expand_filter_expression_builder(&attr_enum_name, &struct_name);
+    let filter_expression_builder = filter_expression::expand_filter_expression_builder(
+        &attr_enum_name,
+        &struct_name,
+        &fields,
+        rename_all_type,
+    );
 
     let transact_write = ops::expand_transact_write(
         &struct_name,
@@ -149,6 +174,9 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
             table_prefix: String,
             table_suffix: String,
             retry_condition: ::raiden::RetryCondition,
+            capacity_accumulator: Option<::raiden::CapacityAccumulator>,
+            metrics: std::sync::Arc<dyn ::raiden::RaidenMetrics>,
+            pipeline: ::raiden::Pipeline,
             attribute_names: Option<::raiden::AttributeNames>,
             projection_expression: Option<String>
         }
@@ -181,7 +209,7 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
 
         impl #client_name {
             pub fn new(region: ::raiden::Region) -> Self {
-                let client = ::raiden::DynamoDbClient::new(region);
+                let client = ::raiden::DynamoDbClient::new_with_client(::raiden::request_id::shared_client(), region);
                 let names = {
                     let mut names: ::raiden::AttributeNames = std::collections::HashMap::new();
                     #(#insertion_attribute_name)*
@@ -195,6 +223,9 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
                     table_suffix: "".to_owned(),
                     client,
                     retry_condition: ::raiden::RetryCondition::new(),
+                    capacity_accumulator: None,
+                    metrics: std::sync::Arc::new(::raiden::NoopMetrics),
+                    pipeline: ::raiden::Pipeline::new(),
                     attribute_names: Some(names),
                     projection_expression
                 }
@@ -205,6 +236,31 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
                 self
             }
 
+            // Calls `f` with the number of the failed attempt, its error and the delay before each retry.
+            pub fn on_retry(mut self, f: impl Fn(usize, &::raiden::RaidenError, std::time::Duration) + Send + Sync + 'static) -> Self {
+                self.retry_condition.on_retry(f);
+                self
+            }
+
+            // Records the capacity consumed by every request of this client into `accumulator`.
+            pub fn with_capacity_accumulator(mut self, accumulator: ::raiden::CapacityAccumulator) -> Self {
+                self.capacity_accumulator = Some(accumulator);
+                self
+            }
+
+            // Reports every operation of this client to `metrics`, e.g. `::raiden::MetricsRecorder`
+            // with the `metrics` feature.
+            pub fn with_metrics(mut self, metrics: impl ::raiden::RaidenMetrics + 'static) -> Self {
+                self.metrics = std::sync::Arc::new(metrics);
+                self
+            }
+
+            // Passes every request of this client through `layer`, after the layers added before.
+            pub fn layer(mut self, layer: impl ::raiden::Layer + 'static) -> Self {
+                self.pipeline.push(layer);
+                self
+            }
+
             pub fn table_prefix(mut self, prefix: impl Into<String>) -> Self {
                 self.table_prefix = prefix.into();
                 self
@@ -232,6 +288,17 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
     proc_macro::TokenStream::from(expanded)
 }
 
+#[proc_macro_derive(RaidenAttribute, attributes(raiden))]
+pub fn derive_raiden_attribute(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+
+    if !finder::include_unary_attr(&input.attrs, "transparent") {
+        panic!("RaidenAttribute requires #[raiden(transparent)]");
+    }
+    let expanded = transparent::expand_transparent(&input.ident, &input.generics, &input.data);
+    proc_macro::TokenStream::from(expanded)
+}
+
 // fn fetch_raiden_field(fields: &syn::FieldsNamed) -> Vec<syn::Field> {
 //     let fields: Vec<syn::Field> = fields
 //         .named
@@ -5,11 +5,15 @@ pub(crate) fn expand_batch_delete(
     partition_key: &(Ident, Type),
     sort_key: &Option<(Ident, Type)>,
     struct_name: &Ident,
+    fields: &FieldsNamed,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}BatchDelete", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchDeleteBuilder", struct_name);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let slot = quote! { &mut error };
+    let pk_attr_value = crate::helpers::key_into_attr(fields, false, quote! { pk.into() }, &slot);
+    let sk_attr_value = crate::helpers::key_into_attr(fields, true, quote! { sk.into() }, &slot);
 
     let client_trait = if let Some(sort_key) = sort_key {
         let (sort_key_ident, sort_key_type) = sort_key;
@@ -20,11 +24,12 @@ pub(crate) fn expand_batch_delete(
 
             impl #trait_name for #client_name {
                 fn batch_delete(&self, keys: std::vec::Vec<(impl Into<#partition_key_type>, impl Into<#sort_key_type>)>) -> #builder_name {
+                    let mut error = None;
                     let write_requests = {
                         let mut write_requests = vec![];
                         for (pk, sk) in keys.into_iter() {
-                            let pk_attr_value = pk.into().into_attr();
-                            let sk_attr_value = sk.into().into_attr();
+                            let pk_attr_value = #pk_attr_value;
+                            let sk_attr_value = #sk_attr_value;
 
                             let write_request = {
                                 let mut write_request = ::raiden::WriteRequest::default();
@@ -46,6 +51,14 @@ pub(crate) fn expand_batch_delete(
 
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
+                        error,
+                        unprocessed_policy: ::raiden::Policy::default(),
                         write_requests,
                         table_name: self.table_name(),
                     }
@@ -60,10 +73,11 @@ pub(crate) fn expand_batch_delete(
 
             impl #trait_name for #client_name {
                 fn batch_delete(&self, keys: std::vec::Vec<impl Into<#partition_key_type>>) -> #builder_name {
+                    let mut error = None;
                     let write_requests = {
                         let mut write_requests = vec![];
                         for pk in keys.into_iter() {
-                            let pk_attr_value = pk.into().into_attr();
+                            let pk_attr_value = #pk_attr_value;
 
                             let write_request = {
                                 let mut write_request = ::raiden::WriteRequest::default();
@@ -84,6 +98,14 @@ pub(crate) fn expand_batch_delete(
 
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
+                        error,
+                        unprocessed_policy: ::raiden::Policy::default(),
                         write_requests,
                         table_name: self.table_name(),
                     }
@@ -97,61 +119,110 @@ pub(crate) fn expand_batch_delete(
 
         pub struct #builder_name<'a> {
             pub client: &'a ::raiden::DynamoDbClient,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
+            // A key which failed to convert, returned by `run`.
+            pub error: Option<::raiden::DeferredConversionError>,
+            pub unprocessed_policy: ::raiden::Policy,
             pub write_requests: std::vec::Vec<::raiden::WriteRequest>,
             pub table_name: String,
         }
 
         impl<'a> #builder_name<'a> {
-            pub async fn run(mut self) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::RaidenError> {
-                // TODO: set the number of retry to 5 for now, which should be made more flexible
-                const RETRY: usize = 5;
-                const MAX_ITEMS_PER_REQUEST: usize = 25;
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
 
-                for _ in 0..RETRY {
-                    loop {
-                        let len = self.write_requests.len();
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
 
-                        // len == 0 means there are no items to be processed anymore
-                        if len == 0 {
-                            break;
-                        }
+            // Retries of items DynamoDB left unprocessed, `Policy::default()` by default.
+            pub fn unprocessed_retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.unprocessed_policy = policy;
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::RaidenError> {
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::BatchWriteItem).with_table_name(self.table_name.clone());
+                let span = ::raiden::OperationSpan::new(&context).with_item_count(self.write_requests.len());
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
 
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::RaidenError> {
+                if let Some(error) = self.error.take() {
+                    return Err(error.into());
+                }
+                const MAX_ITEMS_PER_REQUEST: usize = 25;
+
+                let return_consumed_capacity = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator);
+                let mut consumed_capacity = None;
+                let mut unprocessed_retry = 0;
+                let mut unprocessed_requests = vec![];
+                loop {
+                    while !self.write_requests.is_empty() {
+                        let len = self.write_requests.len();
                         let start = len.saturating_sub(MAX_ITEMS_PER_REQUEST);
-                        let end = std::cmp::min(len, start + MAX_ITEMS_PER_REQUEST);
                         // take requests up to 25 from the request buffer
-                        let req = self.write_requests.drain(start..end).collect::<std::vec::Vec<_>>();
+                        let req = self.write_requests.drain(start..len).collect::<std::vec::Vec<_>>();
                         let request_items = vec![(self.table_name.clone(), req)]
                             .into_iter()
                             .collect::<std::collections::HashMap<_, _>>();
                         let input = ::raiden::BatchWriteItemInput {
                             request_items,
+                            return_consumed_capacity: return_consumed_capacity.clone(),
                             ..std::default::Default::default()
                         };
 
-                        let result = self.client.batch_write_item(input).await?;
-                        let mut unprocessed_items = match result.unprocessed_items {
-                            None => {
-                                // move on to the next iteration to check if there are unprocessed
-                                // requests
-                                continue;
+                        let client = self.client;
+                        let pipeline = self.pipeline;
+                        let result = self.condition.retry(self.policy, attempts, move || {
+                            let client = client.clone();
+                            let input = input.clone();
+                            async move {
+                                pipeline.batch_write_item(&client, input).await
                             }
-                            Some(unprocessed_items) => {
-                                if unprocessed_items.is_empty() {
-                                    // move on to the next iteration to check if there are unprocessed
-                                    // requests
-                                    continue;
-                                }
-
-                                unprocessed_items
-                            },
-                        };
+                        }).await?;
+                        if let Some(accumulator) = self.capacity_accumulator {
+                            accumulator.record_all(&result.consumed_capacity);
+                        }
+                        ::raiden::merge_consumed_capacities(&mut consumed_capacity, &result.consumed_capacity);
+                        if let Some(mut unprocessed_items) = result.unprocessed_items {
+                            if let Some(requests) = unprocessed_items.remove(&self.table_name) {
+                                unprocessed_requests.extend(requests);
+                            }
+                        }
+                    }
 
-                        let unprocessed_requests = unprocessed_items
-                            .remove(&self.table_name)
-                            .expect("reqeust_items hashmap must have a value for the table name");
-                        // push unprocessed requests back to the request buffer
-                        self.write_requests.extend(unprocessed_requests);
+                    if unprocessed_requests.is_empty() {
+                        break;
+                    }
+                    // push unprocessed requests back to the request buffer
+                    self.write_requests.append(&mut unprocessed_requests);
+                    match self.unprocessed_policy.backoff(unprocessed_retry) {
+                        Some(delay) => ::raiden::retry::sleep(delay).await,
+                        None => break,
                     }
+                    unprocessed_retry += 1;
                 }
 
                 // when retry is done the specified times, treat it as success even if there are
@@ -161,7 +232,7 @@ pub(crate) fn expand_batch_delete(
                     .filter_map(|write_request| write_request.delete_request)
                     .collect::<std::vec::Vec<_>>();
                 Ok(::raiden::batch_delete::BatchDeleteOutput {
-                    consumed_capacity: None,
+                    consumed_capacity,
                     unprocessed_items,
                 })
             }
@@ -16,6 +16,10 @@ pub(crate) fn expand_batch_get(
     let builder_name = format_ident!("{}BatchGetItemBuilder", struct_name);
     let from_item = super::expand_attr_to_item(&format_ident!("res_item"), fields, rename_all_type);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let slot = quote! { &mut error };
+    let pk_attr_value = crate::helpers::key_into_attr(fields, false, quote! { pk.into() }, &slot);
+    let sk_attr_value = crate::helpers::key_into_attr(fields, true, quote! { sk.into() }, &slot);
+    let key_attr_value = crate::helpers::key_into_attr(fields, false, quote! { key.into() }, &slot);
 
     let builder_keys_type = if sort_key.is_none() {
         quote! { std::vec::Vec<::raiden::AttributeValue> }
@@ -45,6 +49,14 @@ pub(crate) fn expand_batch_get(
 
         #builder_name {
             client: &self.client,
+            policy: self.retry_condition.strategy.policy(),
+            condition: &self.retry_condition,
+            return_consumed_capacity: None,
+            capacity_accumulator: self.capacity_accumulator.as_ref(),
+            metrics: &*self.metrics,
+            pipeline: &self.pipeline,
+            error,
+            unprocessed_policy: ::raiden::Policy::default(),
             table_name: self.table_name(),
             keys: key_attrs,
             attribute_names: Some(names),
@@ -60,9 +72,10 @@ pub(crate) fn expand_batch_get(
 
             impl #trait_name for #client_name {
                 fn batch_get(&self, keys: std::vec::Vec<impl Into<#partition_key_type>>) -> #builder_name {
+                    let mut error = None;
                     let mut key_attrs = vec![];
                     for key in keys.into_iter() {
-                        key_attrs.push(key.into().into_attr());
+                        key_attrs.push(#key_attr_value);
                     }
 
                     #builder_init
@@ -78,9 +91,10 @@ pub(crate) fn expand_batch_get(
 
             impl #trait_name for #client_name {
                 fn batch_get(&self, keys: std::vec::Vec<(impl Into<#partition_key_type>, impl Into<#sort_key_type>)>) -> #builder_name {
+                    let mut error = None;
                     let mut key_attrs = vec![];
                     for (pk, sk) in keys.into_iter() {
-                        key_attrs.push((pk.into().into_attr(), sk.into().into_attr()));
+                        key_attrs.push((#pk_attr_value, #sk_attr_value));
                     }
 
                     #builder_init
@@ -114,6 +128,15 @@ pub(crate) fn expand_batch_get(
 
         pub struct #builder_name<'a> {
             pub client: &'a ::raiden::DynamoDbClient,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
+            // A key which failed to convert, returned by `run`.
+            pub error: Option<::raiden::DeferredConversionError>,
+            pub unprocessed_policy: ::raiden::Policy,
             pub table_name: String,
             pub keys: #builder_keys_type,
             pub attribute_names: Option<::raiden::AttributeNames>,
@@ -121,14 +144,52 @@ pub(crate) fn expand_batch_get(
         }
 
         impl<'a> #builder_name<'a> {
-
             #![allow(clippy::field_reassign_with_default)]
-            pub async fn run(mut self) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::RaidenError> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
+            // Retries of keys DynamoDB left unprocessed, `Policy::default()` by default.
+            pub fn unprocessed_retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.unprocessed_policy = policy;
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::RaidenError> {
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::BatchGetItem).with_table_name(self.table_name.clone());
+                let span = ::raiden::OperationSpan::new(&context);
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::RaidenError> {
+                if let Some(error) = self.error.take() {
+                    return Err(error.into());
+                }
                 let mut items: std::vec::Vec<#struct_name> = vec![];
                 let mut unprocessed_keys = ::raiden::KeysAndAttributes::default();
+                let return_consumed_capacity = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator);
+                let mut consumed_capacity = None;
 
-                // TODO: for now set 5, however we should make it more flexible.
-                let mut unprocessed_retry = 5;
+                let mut unprocessed_retry = 0;
                 loop {
                     let mut input = ::raiden::BatchGetItemInput::default();
                     let mut item = ::raiden::KeysAndAttributes::default();
@@ -145,16 +206,25 @@ pub(crate) fn expand_batch_get(
                         #convert_to_external_proc
                     }
 
+                    input.return_consumed_capacity = return_consumed_capacity.clone();
                     input.request_items = Default::default();
                     input
                         .request_items
                         .insert(self.table_name.to_string(), item);
 
-                    let res = self.client.batch_get_item(input).await?;
-
-                    if self.keys.is_empty() {
-                        unprocessed_retry -= 1;
+                    let client = self.client;
+                    let pipeline = self.pipeline;
+                    let res = self.condition.retry(self.policy, attempts, move || {
+                        let client = client.clone();
+                        let input = input.clone();
+                        async move {
+                            pipeline.batch_get_item(&client, input).await
+                        }
+                    }).await?;
+                    if let Some(accumulator) = self.capacity_accumulator {
+                        accumulator.record_all(&res.consumed_capacity);
                     }
+                    ::raiden::merge_consumed_capacities(&mut consumed_capacity, &res.consumed_capacity);
 
                     if let Some(res_responses) = &res.responses {
                         if let Some(res_items) = res_responses.get(&self.table_name) {
@@ -179,12 +249,26 @@ pub(crate) fn expand_batch_get(
                     }
 
 
-                    if (self.keys.is_empty() && unprocessed_keys.keys.is_empty()) || unprocessed_retry == 0 {
-                        return Ok(::raiden::batch_get::BatchGetOutput {
-                            consumed_capacity: res.consumed_capacity,
-                            items,
-                            unprocessed_keys: Some(unprocessed_keys),
-                        })
+                    if self.keys.is_empty() {
+                        // Only unprocessed keys are left, so back off before retrying them.
+                        let backoff = if unprocessed_keys.keys.is_empty() {
+                            None
+                        } else {
+                            self.unprocessed_policy.backoff(unprocessed_retry)
+                        };
+                        match backoff {
+                            Some(delay) => {
+                                unprocessed_retry += 1;
+                                ::raiden::retry::sleep(delay).await;
+                            }
+                            None => {
+                                return Ok(::raiden::batch_get::BatchGetOutput {
+                                    consumed_capacity,
+                                    items,
+                                    unprocessed_keys: Some(unprocessed_keys),
+                                })
+                            }
+                        }
                     }
                 }
             }
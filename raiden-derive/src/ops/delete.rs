@@ -6,12 +6,17 @@ pub(crate) fn expand_delete_item(
     partition_key: &(Ident, Type),
     sort_key: &Option<(Ident, Type)>,
     struct_name: &Ident,
+    fields: &FieldsNamed,
 ) -> TokenStream {
     let trait_name = format_ident!("{}DeleteItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}DeleteItemBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let slot = quote! { context.deferred_error() };
+    let pk_attr_value = crate::helpers::key_into_attr(fields, false, quote! { pk.into() }, &slot);
+    let sk_attr_value = crate::helpers::key_into_attr(fields, true, quote! { sk.into() }, &slot);
+    let key_attr_value = crate::helpers::key_into_attr(fields, false, quote! { key.into() }, &slot);
 
     let client_trait = if let Some(sort_key) = sort_key {
         let (sort_key_ident, sort_key_type) = sort_key;
@@ -23,8 +28,9 @@ pub(crate) fn expand_delete_item(
             impl #trait_name for #client_name {
                 fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
                     let mut input = ::raiden::DeleteItemInput::default();
-                    let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
-                    let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
+                    let mut context = ::raiden::ExpressionContext::new();
+                    let pk_attr: ::raiden::AttributeValue = #pk_attr_value;
+                    let sk_attr: ::raiden::AttributeValue = #sk_attr_value;
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                     key_set.insert(stringify!(#partition_key_ident).to_owned(), pk_attr);
                     key_set.insert(stringify!(#sort_key_ident).to_owned(), sk_attr);
@@ -32,7 +38,14 @@ pub(crate) fn expand_delete_item(
                     input.table_name = self.table_name();
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
                         input,
+                        context,
                     }
                 }
             }
@@ -46,14 +59,22 @@ pub(crate) fn expand_delete_item(
             impl #trait_name for #client_name {
                 fn delete(&self, key: impl Into<#partition_key_type>) -> #builder_name {
                     let mut input = ::raiden::DeleteItemInput::default();
-                    let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                    let mut context = ::raiden::ExpressionContext::new();
+                    let key_attr: ::raiden::AttributeValue = #key_attr_value;
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                     key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
                         input,
+                        context,
                     }
                 }
             }
@@ -65,30 +86,81 @@ pub(crate) fn expand_delete_item(
 
         pub struct #builder_name<'a> {
             pub client: &'a ::raiden::DynamoDbClient,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
             pub input: ::raiden::DeleteItemInput,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl<'a> #builder_name<'a> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub fn raw_input(mut self, input: ::raiden::DeleteItemInput) -> Self {
                 self.input = input;
                 self
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
-                }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
 
-            pub async fn run(self) -> Result<(), ::raiden::RaidenError> {
-                let res = self.client.delete_item(self.input).await?;
-                Ok(())
+            pub async fn run(self) -> Result<::raiden::delete::DeleteOutput, ::raiden::RaidenError> {
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::delete::DeleteOutput, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::DeleteItem).with_table_name(self.input.table_name.clone()).with_key(self.input.key.clone());
+                let span = ::raiden::OperationSpan::new(&context).with_item_count(1);
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::delete::DeleteOutput, ::raiden::RaidenError> {
+                self.context.apply(&mut self.input.expression_attribute_names, &mut self.input.expression_attribute_values)?;
+                if let Some(value) = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator) {
+                    self.input.return_consumed_capacity = Some(value);
+                }
+                let client = self.client;
+                let input = self.input;
+                let pipeline = self.pipeline;
+                let res = self.condition.retry(self.policy, attempts, move || {
+                    let client = client.clone();
+                    let input = input.clone();
+                    async move {
+                        pipeline.delete_item(&client, input).await
+                    }
+                }).await?;
+                if let Some(accumulator) = self.capacity_accumulator {
+                    accumulator.record(&res.consumed_capacity);
+                }
+                Ok(::raiden::delete::DeleteOutput {
+                    consumed_capacity: res.consumed_capacity,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
             }
         }
     }
@@ -14,6 +14,10 @@ pub(crate) fn expand_get_item(
     let builder_name = format_ident!("{}GetItemBuilder", struct_name);
     let from_item = super::expand_attr_to_item(&format_ident!("res_item"), fields, rename_all_type);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let slot = quote! { &mut error };
+    let pk_attr_value = crate::helpers::key_into_attr(fields, false, quote! { pk.into() }, &slot);
+    let sk_attr_value = crate::helpers::key_into_attr(fields, true, quote! { sk.into() }, &slot);
+    let key_attr_value = crate::helpers::key_into_attr(fields, false, quote! { key.into() }, &slot);
 
     let client_trait = if let Some(sort_key) = sort_key {
         let (sort_key_ident, sort_key_type) = sort_key;
@@ -24,9 +28,10 @@ pub(crate) fn expand_get_item(
 
             impl #trait_name for #client_name {
                 fn get(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
+                    let mut error = None;
                     let mut input = ::raiden::GetItemInput::default();
-                    let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
-                    let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
+                    let pk_attr: ::raiden::AttributeValue = #pk_attr_value;
+                    let sk_attr: ::raiden::AttributeValue = #sk_attr_value;
                     input.projection_expression = self.projection_expression.clone();
                     input.expression_attribute_names = self.attribute_names.clone();
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
@@ -39,6 +44,11 @@ pub(crate) fn expand_get_item(
                         input,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
+                        error,
                     }
                 }
             }
@@ -51,7 +61,8 @@ pub(crate) fn expand_get_item(
 
             impl #trait_name for #client_name {
                 fn get(&self, key: impl Into<#partition_key_type>) -> #builder_name {
-                    let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                    let mut error = None;
+                    let key_attr: ::raiden::AttributeValue = #key_attr_value;
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                     key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                     let input = ::raiden::GetItemInput {
@@ -67,6 +78,11 @@ pub(crate) fn expand_get_item(
                         input,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
+                        error,
                     }
                 }
             }
@@ -81,29 +97,74 @@ pub(crate) fn expand_get_item(
             pub input: ::raiden::GetItemInput,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
+            // A key which failed to convert, returned by `run`.
+            pub error: Option<::raiden::DeferredConversionError>,
         }
 
         impl<'a> #builder_name<'a> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub fn consistent(mut self) -> Self {
                 self.input.consistent_read = Some(true);
                 self
             }
 
             pub async fn run(self) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
-                let policy: ::raiden::RetryPolicy = self.policy.into();
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::GetItem).with_table_name(self.input.table_name.clone()).with_key(self.input.key.clone());
+                let span = ::raiden::OperationSpan::new(&context);
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
+                if let Some(error) = self.error.take() {
+                    return Err(error.into());
+                }
+                if let Some(value) = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator) {
+                    self.input.return_consumed_capacity = Some(value);
+                }
                 let client = self.client;
                 let input = self.input;
-                policy.retry_if(move || {
+                let pipeline = self.pipeline;
+                let res = self.condition.retry(self.policy, attempts, move || {
                     let client = client.clone();
                     let input = input.clone();
-                    async {
-                        #builder_name::inner_run(client, input).await
+                    async move {
+                        #builder_name::inner_run(pipeline, client, input).await
                     }
-                }, self.condition).await
+                }).await?;
+                if let Some(accumulator) = self.capacity_accumulator {
+                    accumulator.record(&res.consumed_capacity);
+                }
+                Ok(res)
             }
 
-            async fn inner_run(client: ::raiden::DynamoDbClient, input: ::raiden::GetItemInput) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
-                let res = client.get_item(input).await?;
+            async fn inner_run(pipeline: &::raiden::Pipeline, client: ::raiden::DynamoDbClient, input: ::raiden::GetItemInput) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
+                let res = pipeline.get_item(&client, input).await?;
                 if res.item.is_none() {
                     return Err(::raiden::RaidenError::ResourceNotFound("resource not found".to_owned()));
                 };
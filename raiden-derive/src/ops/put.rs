@@ -27,6 +27,19 @@ pub(crate) fn expand_put_item(
             }
         });
 
+    // Attribute names of the partition and sort keys, attached to errors.
+    let key_names: Vec<String> = vec![
+        crate::finder::find_partition_key_field(fields),
+        crate::finder::find_sort_key_field(fields),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|f| {
+        let renamed = crate::finder::find_rename_value(&f.attrs);
+        create_renamed(f.ident.unwrap().to_string(), renamed, rename_all_type)
+    })
+    .collect();
+
     let output_fields = fields.named.iter().map(|f| {
         let ident = &f.ident.clone().unwrap();
         let ty = &f.ty;
@@ -68,9 +81,16 @@ pub(crate) fn expand_put_item(
                     );
                 }
             } else {
+                let value = crate::helpers::collect_into_attr(
+                    &f.attrs,
+                    &f.ty,
+                    quote! { item.#ident.clone() },
+                    &attr_key,
+                    &quote! { context.deferred_error() },
+                );
+                let policy = crate::helpers::empty_value_policy(&f.attrs);
                 quote! {
-                    let value = item.#ident.clone().into_attr();
-                    if !::raiden::is_attr_value_empty(&value) {
+                    if let Some(value) = #policy.apply(#value) {
                         input_item.insert(
                             #attr_key.to_string(),
                             value,
@@ -124,6 +144,7 @@ pub(crate) fn expand_put_item(
                 // let mut attribute_names: std::collections::HashMap<String, String> = std::collections::HashMap::new();
                 // let mut attribute_values: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+                let mut context = ::raiden::ExpressionContext::new();
 
                 #input_items
 
@@ -152,19 +173,45 @@ pub(crate) fn expand_put_item(
                 input.table_name = self.table_name();
                 #builder_name {
                     client: &self.client,
+                    policy: self.retry_condition.strategy.policy(),
+                    condition: &self.retry_condition,
+                    return_consumed_capacity: None,
+                    capacity_accumulator: self.capacity_accumulator.as_ref(),
+                    metrics: &*self.metrics,
+                    pipeline: &self.pipeline,
                     input,
                     item: output_item,
+                    context,
                 }
             }
         }
 
         pub struct #builder_name<'a> {
             pub client: &'a ::raiden::DynamoDbClient,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
             pub input: ::raiden::PutItemInput,
             pub item: #item_output_name,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl<'a> #builder_name<'a> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
 
             pub fn raw_input(mut self, input: ::raiden::PutItemInput) -> Self {
                 self.input = input;
@@ -172,19 +219,48 @@ pub(crate) fn expand_put_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
-                }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
 
             pub async fn run(self) -> Result<::raiden::put::PutOutput<#item_output_name>, ::raiden::RaidenError> {
-                let res = self.client.put_item(self.input).await?;
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::put::PutOutput<#item_output_name>, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::PutItem).with_table_name(self.input.table_name.clone()).with_key(self.input.item.iter().filter(|(name, _)| [#(#key_names),*].contains(&name.as_str())).map(|(name, value)| (name.clone(), value.clone())).collect());
+                let span = ::raiden::OperationSpan::new(&context);
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::put::PutOutput<#item_output_name>, ::raiden::RaidenError> {
+                self.context.apply(&mut self.input.expression_attribute_names, &mut self.input.expression_attribute_values)?;
+                if let Some(value) = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator) {
+                    self.input.return_consumed_capacity = Some(value);
+                }
+                let client = self.client;
+                let input = self.input;
+                let pipeline = self.pipeline;
+                let res = self.condition.retry(self.policy, attempts, move || {
+                    let client = client.clone();
+                    let input = input.clone();
+                    async move {
+                        pipeline.put_item(&client, input).await
+                    }
+                }).await?;
+                if let Some(accumulator) = self.capacity_accumulator {
+                    accumulator.record(&res.consumed_capacity);
+                }
                 Ok(::raiden::put::PutOutput {
                     item: self.item,
                     consumed_capacity: res.consumed_capacity,
@@ -27,6 +27,11 @@ pub(crate) fn expand_query(
             pub limit: Option<i64>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
+            pub context: ::raiden::ExpressionContext,
         }
 
         struct #builder_name_output {
@@ -53,11 +58,28 @@ pub(crate) fn expand_query(
                     limit: None,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
+                    return_consumed_capacity: None,
+                    capacity_accumulator: self.capacity_accumulator.as_ref(),
+                    metrics: &*self.metrics,
+                    pipeline: &self.pipeline,
+                    context: ::raiden::ExpressionContext::new(),
                 }
             }
         }
 
         impl<'a> #builder_name<'a> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub fn index(mut self, index: impl Into<String>) -> Self {
                 self.input.index_name = Some(index.into());
                 self
@@ -89,38 +111,49 @@ pub(crate) fn expand_query(
             }
 
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_values.is_empty() {
-                    if let Some(v) = self.input.expression_attribute_values {
-                        self.input.expression_attribute_values = Some(::raiden::merge_map(attr_values, v));
-                    } else {
-                        self.input.expression_attribute_values = Some(attr_values);
-                    }
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Filter, cond) {
+                    self.input.filter_expression = Some(cond_str);
                 }
-                self.input.filter_expression = Some(cond_str);
                 self
             }
 
             pub fn key_condition(mut self, cond: impl ::raiden::key_condition::KeyConditionBuilder<#key_condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_values.is_empty() {
-                    if let Some(v) = self.input.expression_attribute_values {
-                        self.input.expression_attribute_values = Some(::raiden::merge_map(attr_values, v));
-                    } else {
-                        self.input.expression_attribute_values = Some(attr_values);
-                    }
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::KeyCondition, cond) {
+                    self.input.key_condition_expression = Some(cond_str);
                 }
-                self.input.key_condition_expression = Some(cond_str);
                 self
             }
 
-            pub async fn run(mut self) -> Result<::raiden::query::QueryOutput<#struct_name>, ::raiden::RaidenError> {
+            pub async fn run(self) -> Result<::raiden::query::QueryOutput<#struct_name>, ::raiden::RaidenError> {
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
+
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::query::QueryOutput<#struct_name>, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::Query).with_table_name(self.input.table_name.clone());
+                let span = ::raiden::OperationSpan::new(&context).with_index_name(self.input.index_name.as_deref());
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::query::QueryOutput<#struct_name>, ::raiden::RaidenError> {
+                self.context.apply(&mut self.input.expression_attribute_names, &mut self.input.expression_attribute_values)?;
                 if let Some(token) = self.next_token {
                     self.input.exclusive_start_key = Some(token.into_attr_values()?);
                 }
+                if let Some(value) = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator) {
+                    self.input.return_consumed_capacity = Some(value);
+                }
 
                 let mut items: Vec<#struct_name> = vec![];
-                let policy: ::raiden::RetryPolicy = self.policy.into();
+                let mut consumed_capacity = None;
                 let client = self.client;
 
                 loop {
@@ -131,13 +164,19 @@ pub(crate) fn expand_query(
                     let input = self.input.clone();
                     let client = self.client.clone();
 
-                    let res: #builder_name_output = policy.retry_if(move || {
+                    let pipeline = self.pipeline;
+
+                    let res: #builder_name_output = self.condition.retry(self.policy, attempts, move || {
                         let input = input.clone();
                         let client = client.clone();
-                        async {
-                            #builder_name::inner_run(client, input).await
+                        async move {
+                            #builder_name::inner_run(pipeline, client, input).await
                         }
-                    }, self.condition).await?;
+                    }).await?;
+                    if let Some(accumulator) = self.capacity_accumulator {
+                        accumulator.record(&res.consumed_capacity);
+                    }
+                    ::raiden::merge_consumed_capacity(&mut consumed_capacity, &res.consumed_capacity);
 
                     if let Some(res_items) = res.items {
                         for res_item in res_items.iter() {
@@ -162,7 +201,7 @@ pub(crate) fn expand_query(
                             None
                         };
                         return Ok(::raiden::query::QueryOutput {
-                            consumed_capacity: res.consumed_capacity,
+                            consumed_capacity,
                             count: res.count,
                             items,
                             next_token,
@@ -174,10 +213,11 @@ pub(crate) fn expand_query(
             }
 
             async fn inner_run(
+                pipeline: &::raiden::Pipeline,
                 client: ::raiden::DynamoDbClient,
                 input: ::raiden::QueryInput,
             ) -> Result<#builder_name_output, ::raiden::RaidenError> {
-                let res = client.query(input).await?;
+                let res = pipeline.query(&client, input).await?;
                 Ok(#builder_name_output {
                     consumed_capacity: res.consumed_capacity,
                     count: res.count,
@@ -7,6 +7,8 @@ pub(crate) fn expand_attr_to_item(
 ) -> Vec<proc_macro2::TokenStream> {
     fields.named.iter().map(|f| {
         let ident = &f.ident.clone().unwrap();
+        let policy = crate::helpers::empty_value_policy(&f.attrs);
+        let from_attr = crate::helpers::from_attr(&f.attrs, &f.ty, quote! { #policy.read(item.cloned()) });
         let use_default = crate::finder::include_unary_attr(&f.attrs, "use_default");
         let renamed = crate::finder::find_rename_value(&f.attrs);
         let attr_key  = if let Some(renamed) = renamed {
@@ -16,40 +18,51 @@ pub(crate) fn expand_attr_to_item(
         } else {
             ident.to_string()
         };
-        if crate::finder::is_option(&f.ty) {
+        let ty = &f.ty;
+        // Value used when the attribute is missing or null, and on conversion errors with `default_on_error`.
+        let default = if let Some(default_fn) = crate::helpers::find_default_fn(&f.attrs) {
+            Some(quote! { #default_fn() })
+        } else if use_default {
+            Some(quote! { <#ty>::default() })
+        } else {
+            None
+        };
+        let on_error = if crate::finder::include_unary_attr(&f.attrs, "default_on_error") {
+            let default = default.clone().unwrap_or_else(|| quote! { <#ty>::default() });
+            quote! { ::raiden::use_default_on_conversion_error(#attr_key, e.at_key(#attr_key), || #default) }
+        } else {
+            quote! { return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string(), source: e.at_key(#attr_key) }) }
+        };
+        let converted = quote! {
+            match #from_attr {
+                Ok(converted) => converted,
+                Err(e) => #on_error,
+            }
+        };
+        if let Some(default) = default {
             quote! {
               #ident: {
                 let item = #item_ident.get(#attr_key);
                 if item.is_none() {
-                    None
+                    #default
                 } else {
-                    let converted = ::raiden::FromAttribute::from_attr(item.cloned());
-                    if converted.is_err() {
-                        return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
-                    }
-                    converted.unwrap()
+                  // If null is true, use default value.
+                  if let Some(true) = item.unwrap().null {
+                    #default
+                  } else {
+                    #converted
+                  }
                 }
               },
             }
-        } else if use_default {
-            let ty = &f.ty;
+        } else if crate::finder::is_option(&f.ty) {
             quote! {
               #ident: {
                 let item = #item_ident.get(#attr_key);
                 if item.is_none() {
-                    #ty::default()
+                    None
                 } else {
-                  // If null is true, use default value.
-                  if let Some(true) = item.unwrap().null {
-                    #ty::default()
-                  } else {
-                    let converted = ::raiden::FromAttribute::from_attr(item.cloned());
-                    if converted.is_err() {
-                      // TODO: improve error handling.
-                        return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
-                    }
-                    converted.unwrap()
-                  }
+                    #converted
                 }
               },
             }
@@ -57,12 +70,7 @@ pub(crate) fn expand_attr_to_item(
             quote! {
                 #ident: {
                   let item = #item_ident.get(#attr_key);
-                  let converted = ::raiden::FromAttribute::from_attr(item.cloned());
-                  if converted.is_err() {
-                    // TODO: improve error handling.
-                      return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
-                  }
-                  converted.unwrap()
+                  #converted
                 },
               }
         }
@@ -20,6 +20,24 @@ pub(crate) fn expand_transact_write(
     let condition_check_builder = format_ident!("{}TransactConditionCheckBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let key_attr_value = crate::helpers::key_into_attr(
+        fields,
+        false,
+        quote! { key.into() },
+        &quote! { context.deferred_error() },
+    );
+
+    let key_names: Vec<String> = vec![
+        crate::finder::find_partition_key_field(fields),
+        crate::finder::find_sort_key_field(fields),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|f| {
+        let renamed = crate::finder::find_rename_value(&f.attrs);
+        create_renamed(f.ident.unwrap().to_string(), renamed, rename_all_type)
+    })
+    .collect();
 
     // let (sort_key_ident, sort_key_type) = sort_key;
     // let output_values = fields.named.iter().map(|f| {
@@ -55,9 +73,16 @@ pub(crate) fn expand_transact_write(
                     );
                 }
             } else {
+                let value = crate::helpers::collect_into_attr(
+                    &f.attrs,
+                    &f.ty,
+                    quote! { item.#ident },
+                    &attr_key,
+                    &quote! { context.deferred_error() },
+                );
+                let policy = crate::helpers::empty_value_policy(&f.attrs);
                 quote! {
-                    let value = item.#ident.into_attr();
-                    if !::raiden::is_attr_value_empty(&value) {
+                    if let Some(value) = #policy.apply(#value) {
                         input_item.insert(
                             #attr_key.to_string(),
                             value,
@@ -80,6 +105,7 @@ pub(crate) fn expand_transact_write(
                 let mut attribute_names: std::collections::HashMap<String, String> = std::collections::HashMap::new();
                 let mut attribute_values: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+                let mut context = ::raiden::ExpressionContext::new();
 
                 #input_items
 
@@ -93,13 +119,15 @@ pub(crate) fn expand_transact_write(
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
                     // item: output_item,
+                    context,
                 }
             }
 
             // TODO: Support sort key
             pub fn condition_check(key: impl Into<#partition_key_type>) -> #condition_check_builder {
                 let mut input = ::raiden::ConditionCheck::default();
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                let mut context = ::raiden::ExpressionContext::new();
+                let key_attr: ::raiden::AttributeValue = #key_attr_value;
                 let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                 key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                 input.key = key_set;
@@ -109,13 +137,15 @@ pub(crate) fn expand_transact_write(
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
                     // item: output_item,
+                    context,
                 }
             }
 
             // TODO: Support sort key
             pub fn delete(key: impl Into<#partition_key_type>) -> #delete_builder {
                 let mut input = ::raiden::Delete::default();
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                let mut context = ::raiden::ExpressionContext::new();
+                let key_attr: ::raiden::AttributeValue = #key_attr_value;
                 let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                 key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                 input.key = key_set;
@@ -125,6 +155,7 @@ pub(crate) fn expand_transact_write(
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
                     // item: output_item,
+                    context,
                 }
             }
 
@@ -132,7 +163,8 @@ pub(crate) fn expand_transact_write(
             pub fn update(key: impl Into<#partition_key_type>) -> #update_builder {
                 let mut input = ::raiden::Update::default();
 
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                let mut context = ::raiden::ExpressionContext::new();
+                let key_attr: ::raiden::AttributeValue = #key_attr_value;
                 let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                 key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                 input.key = key_set;
@@ -147,6 +179,7 @@ pub(crate) fn expand_transact_write(
                     set_items: vec![],
                     remove_items: vec![],
                     delete_items: vec![],
+                    context,
                 }
             }
         }
@@ -156,13 +189,19 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::Put,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl ::raiden::TransactWritePutBuilder for #put_builder {
-            fn build(self) -> ::raiden::Put {
+            fn build(self) -> Result<::raiden::Put, ::raiden::RaidenError> {
                 let mut input = self.input;
+                self.context.apply(&mut input.expression_attribute_names, &mut input.expression_attribute_values)?;
                 input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
-                input
+                Ok(input)
+            }
+
+            fn key_names(&self) -> Vec<String> {
+                vec![#(#key_names.to_owned()),*]
             }
         }
 
@@ -179,14 +218,10 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
-                }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
         }
@@ -201,10 +236,11 @@ pub(crate) fn expand_transact_write(
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
             pub remove_items: Vec<#attr_enum_name>,
             pub delete_items: Vec<(#attr_enum_name, ::raiden::AttributeValue)>,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl ::raiden::TransactWriteUpdateBuilder for #update_builder {
-            fn build(mut self) -> ::raiden::Update {
+            fn build(mut self) -> Result<::raiden::Update, ::raiden::RaidenError> {
                 // let mut input = self.input;
 
                 // TODO: Refactor later
@@ -217,7 +253,6 @@ pub(crate) fn expand_transact_write(
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
                 let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
                     let attr_name = format!("#{}", name.into_attr_name());
                     let val = format!("{}", attr_name);
                     attr_names.insert(attr_name, name.into_attr_name());
@@ -256,8 +291,9 @@ pub(crate) fn expand_transact_write(
 
 
 
+                let placeholders = self.context.placeholders();
                 let delete_expression = delete_items.into_iter().map(|(name, value)| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
+                    let placeholder = placeholders.push(value.clone());
                     let attr_name = format!("#{}", name.into_attr_name());
                     let val = format!("{} {}", attr_name, placeholder);
                     attr_names.insert(attr_name, name.into_attr_name());
@@ -280,21 +316,12 @@ pub(crate) fn expand_transact_write(
                 }
                 let update_expression = update_expressions.join(" ");
 
-                if self.input.expression_attribute_names.is_none() {
-                    self.input.expression_attribute_names = Some(attr_names);
-                } else {
-                    self.input.expression_attribute_names = Some(::raiden::merge_map(self.input.expression_attribute_names.unwrap(), attr_names));
-                }
-                if self.input.expression_attribute_values.is_none() {
-                    self.input.expression_attribute_values = Some(attr_values);
-                } else {
-                    self.input.expression_attribute_values = Some(::raiden::merge_map(self.input.expression_attribute_values.unwrap(), attr_values));
-                }
-
+                let update_expression = self.context.register(::raiden::ExpressionClause::Update, (update_expression, attr_names, attr_values));
+                self.context.apply(&mut self.input.expression_attribute_names, &mut self.input.expression_attribute_values)?;
                 self.input.update_expression = update_expression;
 
                 self.input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
-                self.input
+                Ok(self.input)
             }
         }
 
@@ -311,12 +338,12 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(self.context.placeholders()));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(self.context.placeholders()));
                 self
             }
 
@@ -331,14 +358,10 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
-                }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
         }
@@ -348,13 +371,15 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::Delete,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl ::raiden::TransactWriteDeleteBuilder for #delete_builder {
-            fn build(self) -> ::raiden::Delete {
+            fn build(self) -> Result<::raiden::Delete, ::raiden::RaidenError> {
                 let mut input = self.input;
+                self.context.apply(&mut input.expression_attribute_names, &mut input.expression_attribute_values)?;
                 input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
-                input
+                Ok(input)
             }
         }
 
@@ -370,14 +395,10 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
-                }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
         }
@@ -387,13 +408,15 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::ConditionCheck,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl ::raiden::TransactWriteConditionCheckBuilder for #condition_check_builder {
-            fn build(self) -> ::raiden::ConditionCheck {
+            fn build(self) -> Result<::raiden::ConditionCheck, ::raiden::RaidenError> {
                 let mut input = self.input;
+                self.context.apply(&mut input.expression_attribute_names, &mut input.expression_attribute_values)?;
                 input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
-                input
+                Ok(input)
             }
         }
 
@@ -409,14 +432,10 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
-                }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = cond_str;
                 }
-                self.input.condition_expression = cond_str;
                 self
             }
         }
@@ -18,6 +18,10 @@ pub(crate) fn expand_update_item(
     let from_item = super::expand_attr_to_item(&format_ident!("res_item"), fields, rename_all_type);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
     let (partition_key_ident, partition_key_type) = partition_key;
+    let slot = quote! { context.deferred_error() };
+    let pk_attr_value = crate::helpers::key_into_attr(fields, false, quote! { pk.into() }, &slot);
+    let sk_attr_value = crate::helpers::key_into_attr(fields, true, quote! { sk.into() }, &slot);
+    let key_attr_value = crate::helpers::key_into_attr(fields, false, quote! { key.into() }, &slot);
 
     let client_trait = if let Some(sort_key) = sort_key {
         let (sort_key_ident, sort_key_type) = sort_key;
@@ -29,8 +33,9 @@ pub(crate) fn expand_update_item(
             impl #trait_name for #client_name {
                 fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
                     let mut input = ::raiden::UpdateItemInput::default();
-                    let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
-                    let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
+                    let mut context = ::raiden::ExpressionContext::new();
+                    let pk_attr: ::raiden::AttributeValue = #pk_attr_value;
+                    let sk_attr: ::raiden::AttributeValue = #sk_attr_value;
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                     key_set.insert(stringify!(#partition_key_ident).to_owned(), pk_attr);
                     key_set.insert(stringify!(#sort_key_ident).to_owned(), sk_attr);
@@ -38,11 +43,18 @@ pub(crate) fn expand_update_item(
                     input.table_name = self.table_name();
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
                         input,
                         set_items: vec![],
                         add_items: vec![],
                         remove_items: vec![],
                         delete_items: vec![],
+                        context,
                     }
                 }
             }
@@ -56,18 +68,26 @@ pub(crate) fn expand_update_item(
             impl #trait_name for #client_name {
                 fn update(&self, key: impl Into<#partition_key_type>) -> #builder_name {
                     let mut input = ::raiden::UpdateItemInput::default();
-                    let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                    let mut context = ::raiden::ExpressionContext::new();
+                    let key_attr: ::raiden::AttributeValue = #key_attr_value;
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
                     key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
                     #builder_name {
                         client: &self.client,
+                        policy: self.retry_condition.strategy.policy(),
+                        condition: &self.retry_condition,
+                        return_consumed_capacity: None,
+                        capacity_accumulator: self.capacity_accumulator.as_ref(),
+                        metrics: &*self.metrics,
+                        pipeline: &self.pipeline,
                         input,
                         set_items: vec![],
                         add_items: vec![],
                         remove_items: vec![],
                         delete_items: vec![],
+                        context,
                     }
                 }
             }
@@ -106,26 +126,45 @@ pub(crate) fn expand_update_item(
 
         pub struct #builder_name<'a> {
             pub client: &'a ::raiden::DynamoDbClient,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            pub capacity_accumulator: Option<&'a ::raiden::CapacityAccumulator>,
+            pub metrics: &'a dyn ::raiden::RaidenMetrics,
+            pub pipeline: &'a ::raiden::Pipeline,
             pub input: ::raiden::UpdateItemInput,
             pub add_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
             pub remove_items: Vec<#attr_enum_name>,
             pub delete_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
+            pub context: ::raiden::ExpressionContext,
         }
 
         impl<'a> #builder_name<'a> {
+            // Overrides the client's retry policy for this call.
+            pub fn retry(mut self, policy: ::raiden::Policy) -> Self {
+                self.policy = policy;
+                self
+            }
+
+            // Asks DynamoDB to report the capacity consumed by this call.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub fn raw_input(mut self, input: ::raiden::UpdateItemInput) -> Self {
                 self.input = input;
                 self
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(self.context.placeholders()));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(self.context.placeholders()));
                 self
             }
 
@@ -135,7 +174,16 @@ pub(crate) fn expand_update_item(
             }
 
             pub fn delete(mut self, set: impl ::raiden::update_expression::UpdateDeleteExpressionBuilder) -> Self {
-                self.delete_items.push(set.build());
+                self.delete_items.push(set.build_with(self.context.placeholders()));
+                self
+            }
+
+            // Appends every action of an untyped tree, e.g. one parsed with `raiden::expr::parse_update`.
+            pub fn update_expression(mut self, expr: ::raiden::update_expression::UpdateExpr) -> Self {
+                let (add_items, set_items, delete_items) = expr.build_actions_with(self.context.placeholders());
+                self.add_items.extend(add_items);
+                self.set_items.extend(set_items);
+                self.delete_items.extend(delete_items);
                 self
             }
 
@@ -152,14 +200,10 @@ pub(crate) fn expand_update_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
-                if !attr_names.is_empty() {
-                    self.input.expression_attribute_names = Some(attr_names);
+                let cond = cond.try_build_with(self.context.placeholders());
+                if let Some(cond_str) = self.context.try_register(::raiden::ExpressionClause::Condition, cond) {
+                    self.input.condition_expression = Some(cond_str);
                 }
-                if !attr_values.is_empty() {
-                    self.input.expression_attribute_values = Some(attr_values);
-                }
-                self.input.condition_expression = Some(cond_str);
                 self
             }
 
@@ -173,7 +217,6 @@ pub(crate) fn expand_update_item(
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
                 let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
                     let attr_name = format!("#{}", name.into_attr_name());
                     let val = format!("{}", attr_name);
                     attr_names.insert(attr_name, name.into_attr_name());
@@ -240,34 +283,49 @@ pub(crate) fn expand_update_item(
             }
 
 
-            pub async fn run(mut self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
-                let (expression, names, values) = self.build_expression();
-                if self.input.expression_attribute_names.is_none() {
-                    if names.is_empty() {
-                        self.input.expression_attribute_names = None;
-                    } else {
-                        self.input.expression_attribute_names = Some(names);
-                    }
-                } else {
-                    self.input.expression_attribute_names = Some(::raiden::merge_map(self.input.expression_attribute_names.unwrap(), names));
-                }
+            pub async fn run(self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+                self.run_with_context().await.map_err(::raiden::RaidenError::from)
+            }
 
-                if self.input.expression_attribute_values.is_none() {
-                    if values.is_empty() {
-                        self.input.expression_attribute_values = None;
-                    } else {
-                        self.input.expression_attribute_values = Some(values);
-                    }
-                } else {
-                    self.input.expression_attribute_values = Some(::raiden::merge_map(self.input.expression_attribute_values.unwrap(), values));
-                }
+            // Same as `run`, but the error tells where it happened.
+            pub async fn run_with_context(self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::ErrorWithContext> {
+                let context = ::raiden::ErrorContext::new(::raiden::Operation::UpdateItem).with_table_name(self.input.table_name.clone()).with_key(self.input.key.clone());
+                let span = ::raiden::OperationSpan::new(&context);
+                let metrics = self.metrics;
+                let attempts = ::raiden::Attempts::default();
+                let request_id = ::raiden::request_id::RequestIdSlot::default();
+                let result = span.instrument(request_id.scope(self.execute(&attempts))).await;
+                span.finish(attempts.get(), &result);
+                metrics.record(&::raiden::OperationMetrics::new(&context, &result, attempts.retries(), span.elapsed()));
+                result.map_err(|e| e.with_context(context.with_attempts(attempts.get()).with_request_id(request_id.get())))
+            }
+
+            async fn execute(mut self, attempts: &::raiden::Attempts) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+                let update = self.build_expression();
+                let expression = self.context.register(::raiden::ExpressionClause::Update, update);
+                self.context.apply(&mut self.input.expression_attribute_names, &mut self.input.expression_attribute_values)?;
 
                 if expression != "" {
                     self.input.update_expression = Some(expression);
                 }
 
+                if let Some(value) = ::raiden::ReturnConsumedCapacity::resolve(self.return_consumed_capacity, self.capacity_accumulator) {
+                    self.input.return_consumed_capacity = Some(value);
+                }
                 let has_return_values = self.input.return_values.is_some();
-                let res = self.client.update_item(self.input).await?;
+                let client = self.client;
+                let input = self.input;
+                let pipeline = self.pipeline;
+                let res = self.condition.retry(self.policy, attempts, move || {
+                    let client = client.clone();
+                    let input = input.clone();
+                    async move {
+                        pipeline.update_item(&client, input).await
+                    }
+                }).await?;
+                if let Some(accumulator) = self.capacity_accumulator {
+                    accumulator.record(&res.consumed_capacity);
+                }
 
                 let item = if has_return_values {
                     let res_item = &res.attributes.unwrap();
@@ -0,0 +1,88 @@
+use quote::*;
+
+// Conversions of a single-field tuple struct annotated with `#[raiden(transparent)]`,
+// which is stored exactly like the wrapped type.
+pub(crate) fn expand_transparent(
+    name: &proc_macro2::Ident,
+    generics: &syn::Generics,
+    data: &syn::Data,
+) -> proc_macro2::TokenStream {
+    let inner = match data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Unnamed(fields),
+            ..
+        }) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+        _ => panic!(
+            "#[raiden(transparent)] is only supported for a tuple struct with a single field"
+        ),
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // A generic wrapper converts only when its field does.
+    let into_where_clause = with_bound(generics, quote! { #inner: ::raiden::IntoAttribute });
+    let from_where_clause = with_bound(generics, quote! { #inner: ::raiden::FromAttribute });
+
+    // `get("id")` and string set items are only available when wrapping a `String`.
+    let string_conversions = if is_string(inner) {
+        quote! {
+            impl #impl_generics From<&str> for #name #ty_generics #where_clause {
+                fn from(value: &str) -> Self {
+                    #name(value.to_owned())
+                }
+            }
+
+            impl #impl_generics ::raiden::IntoStringSetItem for #name #ty_generics #where_clause {
+                fn into_ss_item(self) -> String {
+                    self.0
+                }
+            }
+
+            impl #impl_generics ::raiden::FromStringSetItem for #name #ty_generics #where_clause {
+                fn from_ss_item(value: String) -> Result<Self, ::raiden::ConversionError> {
+                    Ok(#name(value))
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        impl #impl_generics ::raiden::IntoAttribute for #name #ty_generics #into_where_clause {
+            fn into_attr(self) -> ::raiden::AttributeValue {
+                ::raiden::IntoAttribute::into_attr(self.0)
+            }
+        }
+
+        impl #impl_generics ::raiden::FromAttribute for #name #ty_generics #from_where_clause {
+            fn from_attr(value: Option<::raiden::AttributeValue>) -> Result<Self, ::raiden::ConversionError> {
+                <#inner as ::raiden::FromAttribute>::from_attr(value).map(#name)
+            }
+        }
+
+        impl #impl_generics From<#inner> for #name #ty_generics #where_clause {
+            fn from(value: #inner) -> Self {
+                #name(value)
+            }
+        }
+
+        #string_conversions
+    }
+}
+
+fn with_bound(generics: &syn::Generics, bound: proc_macro2::TokenStream) -> syn::WhereClause {
+    let mut generics = generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote! { #bound });
+    generics.where_clause.unwrap()
+}
+
+fn is_string(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            matches!(path.segments.last(), Some(s) if s.ident == "String")
+        }
+        _ => false,
+    }
+}
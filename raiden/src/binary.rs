@@ -0,0 +1,120 @@
+use crate::errors::take_attr;
+use crate::{AttributeType, AttributeValue, ConversionError, FromAttribute, IntoAttribute};
+use bytes::Bytes;
+
+// Binary attribute (`B`). `Vec<u8>` on its own is stored as a list of numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Binary(pub Vec<u8>);
+
+impl Binary {
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl std::ops::Deref for Binary {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Binary {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Binary {
+    fn from(value: Vec<u8>) -> Self {
+        Binary(value)
+    }
+}
+
+impl From<&[u8]> for Binary {
+    fn from(value: &[u8]) -> Self {
+        Binary(value.to_vec())
+    }
+}
+
+impl From<Bytes> for Binary {
+    fn from(value: Bytes) -> Self {
+        Binary(value.to_vec())
+    }
+}
+
+impl From<Binary> for Bytes {
+    fn from(value: Binary) -> Self {
+        Bytes::from(value.0)
+    }
+}
+
+impl IntoAttribute for Bytes {
+    fn into_attr(self) -> AttributeValue {
+        // Empty binary is allowed for non-key attributes since 2020/5.
+        AttributeValue {
+            b: Some(self),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+impl FromAttribute for Bytes {
+    fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        take_attr(value, AttributeType::B, |value| {
+            if let Some(true) = value.null {
+                return Some(Bytes::new());
+            }
+            value.b.take()
+        })
+    }
+}
+
+impl IntoAttribute for Binary {
+    fn into_attr(self) -> AttributeValue {
+        Bytes::from(self).into_attr()
+    }
+}
+
+impl FromAttribute for Binary {
+    fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        Bytes::from_attr(value).map(Binary::from)
+    }
+}
+
+macro_rules! default_binary_set_convertor {
+    ($to: ty) => {
+        impl IntoAttribute for std::collections::HashSet<$to> {
+            fn into_attr(self) -> AttributeValue {
+                if self.is_empty() {
+                    // See. https://github.com/raiden-rs/raiden/issues/57
+                    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
+                    return AttributeValue::default();
+                }
+                AttributeValue {
+                    bs: Some(self.into_iter().map(Bytes::from).collect()),
+                    ..AttributeValue::default()
+                }
+            }
+        }
+
+        impl FromAttribute for std::collections::HashSet<$to> {
+            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+                if value.is_none() {
+                    return Ok(std::collections::HashSet::new());
+                }
+                let value = value.unwrap();
+                if let Some(true) = value.null {
+                    // See. https://github.com/raiden-rs/raiden/issues/57
+                    return Ok(std::collections::HashSet::new());
+                }
+                let bs = take_attr(Some(value), AttributeType::BS, |value| value.bs.take())?;
+                Ok(bs.into_iter().map(<$to>::from).collect())
+            }
+        }
+    };
+}
+
+default_binary_set_convertor!(Bytes);
+default_binary_set_convertor!(Binary);
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{Capacity, ConsumedCapacity};
+
+// Level of detail of the consumed capacity returned by DynamoDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnConsumedCapacity {
+    // Capacity consumed by the operation as a whole.
+    Total,
+    // The total along with the capacity consumed by each table and index.
+    Indexes,
+}
+
+impl ReturnConsumedCapacity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReturnConsumedCapacity::Total => "TOTAL",
+            ReturnConsumedCapacity::Indexes => "INDEXES",
+        }
+    }
+
+    // The value sent with a request. A builder's own setting wins, and `TOTAL` is requested
+    // when a `CapacityAccumulator` is attached to the client.
+    pub fn resolve(
+        value: Option<ReturnConsumedCapacity>,
+        accumulator: Option<&CapacityAccumulator>,
+    ) -> Option<String> {
+        match (value, accumulator) {
+            (Some(value), _) => Some(value.as_str().to_owned()),
+            (None, Some(_)) => Some(ReturnConsumedCapacity::Total.as_str().to_owned()),
+            (None, None) => None,
+        }
+    }
+}
+
+// Adds `other` into `total`, e.g. for each page of an auto-paginated query.
+pub fn merge_consumed_capacity(
+    total: &mut Option<ConsumedCapacity>,
+    other: &Option<ConsumedCapacity>,
+) {
+    if let Some(other) = other {
+        match total {
+            Some(total) => add_consumed_capacity(total, other),
+            None => *total = Some(other.clone()),
+        }
+    }
+}
+
+// Adds `other` into `total` table by table, e.g. for each request of a batch.
+pub fn merge_consumed_capacities(
+    total: &mut Option<Vec<ConsumedCapacity>>,
+    other: &Option<Vec<ConsumedCapacity>>,
+) {
+    if let Some(other) = other {
+        let total = total.get_or_insert_with(Vec::new);
+        for capacity in other.iter() {
+            match total
+                .iter_mut()
+                .find(|c| c.table_name == capacity.table_name)
+            {
+                Some(c) => add_consumed_capacity(c, capacity),
+                None => total.push(capacity.clone()),
+            }
+        }
+    }
+}
+
+fn add_consumed_capacity(total: &mut ConsumedCapacity, other: &ConsumedCapacity) {
+    total.capacity_units = add_units(total.capacity_units, other.capacity_units);
+    total.read_capacity_units = add_units(total.read_capacity_units, other.read_capacity_units);
+    total.write_capacity_units = add_units(total.write_capacity_units, other.write_capacity_units);
+    add_optional_capacity(&mut total.table, &other.table);
+    add_index_capacities(
+        &mut total.global_secondary_indexes,
+        &other.global_secondary_indexes,
+    );
+    add_index_capacities(
+        &mut total.local_secondary_indexes,
+        &other.local_secondary_indexes,
+    );
+}
+
+fn add_index_capacities(
+    total: &mut Option<HashMap<String, Capacity>>,
+    other: &Option<HashMap<String, Capacity>>,
+) {
+    if let Some(other) = other {
+        let total = total.get_or_insert_with(HashMap::new);
+        for (index, capacity) in other.iter() {
+            add_capacity(total.entry(index.clone()).or_default(), capacity);
+        }
+    }
+}
+
+fn add_optional_capacity(total: &mut Option<Capacity>, other: &Option<Capacity>) {
+    if let Some(other) = other {
+        match total {
+            Some(total) => add_capacity(total, other),
+            None => *total = Some(other.clone()),
+        }
+    }
+}
+
+fn add_capacity(total: &mut Capacity, other: &Capacity) {
+    total.capacity_units = add_units(total.capacity_units, other.capacity_units);
+    total.read_capacity_units = add_units(total.read_capacity_units, other.read_capacity_units);
+    total.write_capacity_units = add_units(total.write_capacity_units, other.write_capacity_units);
+}
+
+fn add_units(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+    }
+}
+
+// Collects the capacity consumed by every request of the clients it's attached to, by table.
+// Clones share the same totals, so one can be attached to several clients and read once a
+// request handler finishes, e.g. for cost attribution.
+#[derive(Debug, Clone, Default)]
+pub struct CapacityAccumulator {
+    consumed: Arc<Mutex<Option<Vec<ConsumedCapacity>>>>,
+}
+
+impl CapacityAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, capacity: &Option<ConsumedCapacity>) {
+        self.record_all(&capacity.clone().map(|capacity| vec![capacity]));
+    }
+
+    pub fn record_all(&self, capacities: &Option<Vec<ConsumedCapacity>>) {
+        merge_consumed_capacities(&mut self.lock(), capacities);
+    }
+
+    // Capacity consumed so far, by table.
+    pub fn consumed_capacity(&self) -> Vec<ConsumedCapacity> {
+        self.lock().clone().unwrap_or_default()
+    }
+
+    pub fn total_capacity_units(&self) -> f64 {
+        self.consumed_capacity()
+            .iter()
+            .filter_map(|c| c.capacity_units)
+            .sum()
+    }
+
+    // Returns the capacity consumed so far and starts over.
+    pub fn take(&self) -> Vec<ConsumedCapacity> {
+        self.lock().take().unwrap_or_default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Vec<ConsumedCapacity>>> {
+        self.consumed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    fn consumed(table_name: &str, units: f64) -> ConsumedCapacity {
+        ConsumedCapacity {
+            table_name: Some(table_name.to_owned()),
+            capacity_units: Some(units),
+            ..ConsumedCapacity::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_consumed_capacity() {
+        let mut total = None;
+        merge_consumed_capacity(&mut total, &Some(consumed("user", 0.5)));
+        merge_consumed_capacity(&mut total, &None);
+        merge_consumed_capacity(&mut total, &Some(consumed("user", 1.0)));
+        assert_eq!(total, Some(consumed("user", 1.5)));
+    }
+
+    #[test]
+    fn test_merge_consumed_capacity_of_indexes() {
+        let mut indexes = HashMap::new();
+        indexes.insert(
+            "nameIndex".to_owned(),
+            Capacity {
+                read_capacity_units: Some(1.0),
+                ..Capacity::default()
+            },
+        );
+        let capacity = ConsumedCapacity {
+            global_secondary_indexes: Some(indexes),
+            ..consumed("user", 1.0)
+        };
+        let mut total = Some(capacity.clone());
+        merge_consumed_capacity(&mut total, &Some(capacity));
+        let total = total.unwrap();
+        assert_eq!(total.capacity_units, Some(2.0));
+        assert_eq!(
+            total.global_secondary_indexes.unwrap()["nameIndex"].read_capacity_units,
+            Some(2.0)
+        );
+    }
+
+    #[test]
+    fn test_merge_consumed_capacities_by_table() {
+        let mut total = None;
+        merge_consumed_capacities(
+            &mut total,
+            &Some(vec![consumed("user", 1.0), consumed("project", 2.0)]),
+        );
+        merge_consumed_capacities(&mut total, &Some(vec![consumed("user", 3.0)]));
+        assert_eq!(
+            total,
+            Some(vec![consumed("user", 4.0), consumed("project", 2.0)])
+        );
+    }
+
+    #[test]
+    fn test_accumulator_is_shared_by_clones() {
+        let accumulator = CapacityAccumulator::new();
+        let cloned = accumulator.clone();
+        cloned.record(&Some(consumed("user", 1.0)));
+        accumulator.record_all(&Some(vec![consumed("project", 2.0)]));
+        assert_eq!(accumulator.total_capacity_units(), 3.0);
+        assert_eq!(
+            cloned.take(),
+            vec![consumed("user", 1.0), consumed("project", 2.0)]
+        );
+        assert_eq!(accumulator.consumed_capacity(), vec![]);
+    }
+
+    #[test]
+    fn test_resolve_return_consumed_capacity() {
+        let accumulator = CapacityAccumulator::new();
+        assert_eq!(ReturnConsumedCapacity::resolve(None, None), None);
+        assert_eq!(
+            ReturnConsumedCapacity::resolve(None, Some(&accumulator)),
+            Some("TOTAL".to_owned())
+        );
+        assert_eq!(
+            ReturnConsumedCapacity::resolve(
+                Some(ReturnConsumedCapacity::Indexes),
+                Some(&accumulator)
+            ),
+            Some("INDEXES".to_owned())
+        );
+    }
+}
@@ -13,20 +13,24 @@ pub enum ConditionFunctionExpression {
     Size(AttrName),
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConditionComparisonExpression {
-    Eq(
-        AttrOrPlaceholder,
-        Option<super::AttributeValue>,
-        AttrOrPlaceholder,
-        Option<super::AttributeValue>,
-    ),
+    Eq(AttrOrValue, AttrOrValue),
+    Ne(AttrOrValue, AttrOrValue),
+    Lt(AttrOrValue, AttrOrValue),
+    Le(AttrOrValue, AttrOrValue),
+    Gt(AttrOrValue, AttrOrValue),
+    Ge(AttrOrValue, AttrOrValue),
+    Between(AttrOrValue, AttrOrValue, AttrOrValue),
 }
 
 #[derive(Clone, PartialEq)]
 pub struct ConditionFilledOrWaitOperator<T: Clone> {
     pub not: bool,
     pub cond: Cond,
+    // A value which failed to convert, reported by the builder this condition is passed to.
+    pub error: Option<DeferredConversionError>,
     pub _token: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -35,213 +39,314 @@ pub struct ConditionFilled<T: Clone> {
     pub not: bool,
     pub cond: Cond,
     pub operator: Operator,
+    pub error: Option<DeferredConversionError>,
     pub _token: std::marker::PhantomData<fn() -> T>,
 }
 
 impl<T: Clone> ConditionFilledOrWaitOperator<T> {
     pub fn and(self, cond: impl ConditionBuilder<T>) -> ConditionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        ConditionFilled {
-            not: self.not,
-            cond: self.cond,
-            operator: Operator::And(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
+        self.join(cond, Operator::And)
     }
     pub fn or(self, cond: impl ConditionBuilder<T>) -> ConditionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
+        self.join(cond, Operator::Or)
+    }
+
+    fn join(
+        self,
+        cond: impl ConditionBuilder<T>,
+        operator: fn(Box<ConditionExpr>) -> Operator,
+    ) -> ConditionFilled<T> {
+        let error = self.error.or_else(|| cond.conversion_error().cloned());
         ConditionFilled {
             not: self.not,
             cond: self.cond,
-            operator: Operator::Or(condition_string, attr_names, attr_values),
+            operator: operator(Box::new(cond.into_expr())),
+            error,
             _token: self._token,
         }
     }
 }
 
 impl<T: Clone> ConditionBuilder<T> for ConditionFilledOrWaitOperator<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        if self.not {
-            (
-                format!("NOT ({})", self.cond.to_string()),
-                self.cond.to_attr_names(),
-                self.cond.into_attr_values(),
-            )
-        } else {
-            (
-                self.cond.to_string(),
-                self.cond.to_attr_names(),
-                self.cond.into_attr_values(),
-            )
+    fn into_expr(self) -> ConditionExpr {
+        ConditionExpr {
+            not: self.not,
+            cond: self.cond,
+            operator: None,
         }
     }
+
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        self.error.as_ref()
+    }
 }
+
 impl<T: Clone> ConditionBuilder<T> for ConditionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let (right_str, right_names, right_values) = match self.operator {
-            super::condition::Operator::And(s, m, v) => (format!("AND ({})", s), m, v),
-            super::condition::Operator::Or(s, m, v) => (format!("OR ({})", s), m, v),
-        };
-        let left_str = self.cond.to_string();
-        let left_names = self.cond.to_attr_names();
-        let left_values = self.cond.into_attr_values();
-        if self.not {
-            (
-                format!("NOT ({}) {}", left_str, right_str),
-                super::merge_map(left_names, right_names),
-                super::merge_map(left_values, right_values),
-            )
-        } else {
-            (
-                format!("{} {}", left_str, right_str),
-                super::merge_map(left_names, right_names),
-                super::merge_map(left_values, right_values),
-            )
+    fn into_expr(self) -> ConditionExpr {
+        ConditionExpr {
+            not: self.not,
+            cond: self.cond,
+            operator: Some(self.operator),
         }
     }
+
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        self.error.as_ref()
+    }
 }
 
-impl std::string::ToString for ConditionFunctionExpression {
-    fn to_string(&self) -> String {
-        use crypto::digest::Digest;
-        use crypto::md5::Md5;
-        match self {
+// Per-field condition whose values are typed by the field, e.g. `User::cond().age().gt(30u32)`.
+#[derive(Clone)]
+pub struct TypedCondition<T: Clone, V> {
+    not: bool,
+    attr: String,
+    encode: ValueEncoder<V>,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Clone, V: IntoAttribute> TypedCondition<T, V> {
+    pub fn new(attr: String) -> Self {
+        Self::with_encoder(attr, encode_into_attr::<V>)
+    }
+}
+
+impl<T: Clone, V> TypedCondition<T, V> {
+    // For a field which isn't stored with `IntoAttribute`.
+    pub fn with_encoder(attr: String, encode: ValueEncoder<V>) -> Self {
+        Self {
+            not: false,
+            attr,
+            encode,
+            _token: std::marker::PhantomData,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(mut self) -> Self {
+        self.not = true;
+        self
+    }
+
+    fn filled(
+        self,
+        cond: Cond,
+        error: Option<DeferredConversionError>,
+    ) -> ConditionFilledOrWaitOperator<T> {
+        ConditionFilledOrWaitOperator {
+            not: self.not,
+            cond,
+            error,
+            _token: self._token,
+        }
+    }
+
+    fn value(&self, value: V, error: &mut Option<DeferredConversionError>) -> AttrOrValue {
+        AttrOrValue::Value(DeferredConversionError::collect(
+            error,
+            &self.attr,
+            (self.encode)(value),
+        ))
+    }
+
+    fn cmp(
+        self,
+        op: fn(AttrOrValue, AttrOrValue) -> ConditionComparisonExpression,
+        value: V,
+    ) -> ConditionFilledOrWaitOperator<T> {
+        let mut error = None;
+        let value = self.value(value, &mut error);
+        let attr = AttrOrValue::Attr(self.attr.clone());
+        self.filled(Cond::Cmp(op(attr, value)), error)
+    }
+
+    pub fn eq(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Eq, value)
+    }
+
+    pub fn ne(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Ne, value)
+    }
+
+    pub fn lt(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Lt, value)
+    }
+
+    pub fn le(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Le, value)
+    }
+
+    pub fn gt(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Gt, value)
+    }
+
+    pub fn ge(self, value: V) -> ConditionFilledOrWaitOperator<T> {
+        self.cmp(ConditionComparisonExpression::Ge, value)
+    }
+
+    pub fn between(self, value1: V, value2: V) -> ConditionFilledOrWaitOperator<T> {
+        let mut error = None;
+        let cond = ConditionComparisonExpression::Between(
+            AttrOrValue::Attr(self.attr.clone()),
+            self.value(value1, &mut error),
+            self.value(value2, &mut error),
+        );
+        self.filled(Cond::Cmp(cond), error)
+    }
+
+    pub fn exists(self) -> ConditionFilledOrWaitOperator<T> {
+        let cond = ConditionFunctionExpression::AttributeExists(self.attr.clone());
+        self.filled(Cond::Func(cond), None)
+    }
+
+    pub fn not_exists(self) -> ConditionFilledOrWaitOperator<T> {
+        let cond = ConditionFunctionExpression::AttributeNotExists(self.attr.clone());
+        self.filled(Cond::Func(cond), None)
+    }
+}
+
+// Untyped condition tree. Values are kept as is and placeholders are allocated on rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionExpr {
+    pub not: bool,
+    pub cond: Cond,
+    pub operator: Option<Operator>,
+}
+
+impl ConditionExpr {
+    pub fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        let (left_str, left_names, left_values) = self.cond.render(placeholders);
+        let left_str = if self.not {
+            format!("NOT ({})", left_str)
+        } else {
+            left_str
+        };
+        let (right_str, right_names, right_values) = match &self.operator {
+            Some(Operator::And(expr)) => {
+                let (s, m, v) = expr.render(placeholders);
+                (format!("AND ({})", s), m, v)
+            }
+            Some(Operator::Or(expr)) => {
+                let (s, m, v) = expr.render(placeholders);
+                (format!("OR ({})", s), m, v)
+            }
+            None => return (left_str, left_names, left_values),
+        };
+        (
+            format!("{} {}", left_str, right_str),
+            super::merge_map(left_names, right_names),
+            super::merge_map(left_values, right_values),
+        )
+    }
+}
+
+impl ConditionFunctionExpression {
+    fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
+        let mut names: super::AttributeNames = std::collections::HashMap::new();
+        let mut values: super::AttributeValues = std::collections::HashMap::new();
+        let mut push = |value: super::AttributeValue| {
+            let placeholder = placeholders.push(value.clone());
+            values.insert(placeholder.clone(), value);
+            placeholder
+        };
+        let s = match self {
             Self::AttributeExists(path) => format!("attribute_exists(#{})", path),
             Self::AttributeNotExists(path) => format!("attribute_not_exists(#{})", path),
             Self::AttributeType(path, attribute_type) => {
-                format!("attribute_type(#{}, :type{})", path, attribute_type)
+                let placeholder = push(attribute_type.clone().into_attr());
+                format!("attribute_type(#{}, {})", path, placeholder)
             }
             Self::BeginsWith(path, s) => {
-                let mut md5 = Md5::new();
-                md5.input(s.as_bytes());
-                format!("begins_with(#{}, :begins_with_{})", path, md5.result_str())
+                let placeholder = push(s.clone().into_attr());
+                format!("begins_with(#{}, {})", path, placeholder)
             }
             Self::Contains(path, s) => {
-                let mut md5 = Md5::new();
-                md5.input(s.as_bytes());
-                format!("contains(#{}, :contains_{})", path, md5.result_str())
+                let placeholder = push(s.clone().into_attr());
+                format!("contains(#{}, {})", path, placeholder)
             }
             Self::Size(_path) => {
                 unimplemented!("Size condition expression is not implemented yet.")
             }
-        }
-    }
-}
-
-impl super::ToAttrNames for ConditionFunctionExpression {
-    fn to_attr_names(&self) -> super::AttributeNames {
-        let mut m: super::AttributeNames = std::collections::HashMap::new();
+        };
         match self {
             Self::Contains(path, _)
             | Self::BeginsWith(path, _)
             | Self::AttributeType(path, _)
             | Self::AttributeExists(path)
             | Self::AttributeNotExists(path) => {
-                m.insert(format!("#{}", path), path.clone());
+                names.insert(format!("#{}", path), path.clone());
             }
             _ => {}
         }
-        m
+        (s, names, values)
     }
 }
 
-impl super::IntoAttrValues for ConditionFunctionExpression {
-    fn into_attr_values(self) -> super::AttributeValues {
-        use crypto::digest::Digest;
-        use crypto::md5::Md5;
-        let mut m: super::AttributeValues = std::collections::HashMap::new();
-        match self {
-            Self::AttributeType(_path, t) => {
-                m.insert(
-                    format!(":type{}", t),
-                    super::AttributeValue {
-                        s: Some(t.to_string()),
-                        ..super::AttributeValue::default()
-                    },
-                );
-            }
-            Self::BeginsWith(_path, s) => {
-                let mut md5 = Md5::new();
-                md5.input(s.as_bytes());
-                m.insert(
-                    format!(":begins_with_{}", md5.result_str()),
-                    super::AttributeValue {
-                        s: Some(s),
-                        ..super::AttributeValue::default()
-                    },
-                );
-            }
-            Self::Contains(_path, s) => {
-                let mut md5 = Md5::new();
-                md5.input(s.as_bytes());
-                m.insert(
-                    format!(":contains_{}", md5.result_str()),
-                    super::AttributeValue {
-                        s: Some(s),
-                        ..super::AttributeValue::default()
-                    },
+impl ConditionComparisonExpression {
+    fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
+        let mut names: super::AttributeNames = std::collections::HashMap::new();
+        let mut values: super::AttributeValues = std::collections::HashMap::new();
+        let (left, op, right) = match self {
+            Self::Eq(left, right) => (left, "=", right),
+            Self::Ne(left, right) => (left, "<>", right),
+            Self::Lt(left, right) => (left, "<", right),
+            Self::Le(left, right) => (left, "<=", right),
+            Self::Gt(left, right) => (left, ">", right),
+            Self::Ge(left, right) => (left, ">=", right),
+            Self::Between(attr, low, high) => {
+                let attr = attr.render(placeholders, &mut names, &mut values);
+                let low = low.render(placeholders, &mut names, &mut values);
+                let high = high.render(placeholders, &mut names, &mut values);
+                return (
+                    format!("{} BETWEEN {} AND {}", attr, low, high),
+                    names,
+                    values,
                 );
             }
-            _ => {}
-        }
-        m
-    }
-}
-
-impl std::string::ToString for ConditionComparisonExpression {
-    fn to_string(&self) -> String {
-        match self {
-            Self::Eq(left, _, right, _) => format!("{} = {}", left.to_string(), right.to_string()),
-        }
-    }
-}
-
-impl super::ToAttrNames for ConditionComparisonExpression {
-    fn to_attr_names(&self) -> super::AttributeNames {
-        let mut m: super::AttributeNames = std::collections::HashMap::new();
-        match self {
-            Self::Eq(left, _, right, _) => {
-                if let AttrOrPlaceholder::Attr(l) = left {
-                    m.insert(left.to_string(), l.clone());
-                }
-                if let AttrOrPlaceholder::Attr(r) = right {
-                    m.insert(right.to_string(), r.clone());
-                }
-            }
-        }
-        m
-    }
-}
-
-impl super::IntoAttrValues for ConditionComparisonExpression {
-    fn into_attr_values(self) -> super::AttributeValues {
-        let mut m: super::AttributeValues = std::collections::HashMap::new();
-
-        match self {
-            Self::Eq(left, left_value, right, right_value) => {
-                if let Some(left_value) = left_value {
-                    m.insert(left.to_string(), left_value);
-                }
-                if let Some(right_value) = right_value {
-                    m.insert(right.to_string(), right_value);
-                }
-            }
-        }
-        m
+        };
+        let left = left.render(placeholders, &mut names, &mut values);
+        let right = right.render(placeholders, &mut names, &mut values);
+        (format!("{} {} {}", left, op, right), names, values)
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
-pub enum AttrOrPlaceholder {
+pub enum AttrOrValue {
     Attr(String),
-    Placeholder(String),
+    Value(super::AttributeValue),
 }
 
-impl std::string::ToString for AttrOrPlaceholder {
-    fn to_string(&self) -> String {
+impl AttrOrValue {
+    pub(crate) fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+        names: &mut super::AttributeNames,
+        values: &mut super::AttributeValues,
+    ) -> String {
         match self {
-            Self::Placeholder(p) => format!(":{}", p),
-            Self::Attr(a) => format!("#{}", a),
+            Self::Attr(a) => {
+                let name = format!("#{}", a);
+                names.insert(name.clone(), a.clone());
+                name
+            }
+            Self::Value(v) => {
+                let placeholder = placeholders.push(v.clone());
+                values.insert(placeholder.clone(), v.clone());
+                placeholder
+            }
         }
     }
 }
@@ -250,16 +355,8 @@ pub type ConditionString = String;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Operator {
-    And(
-        ConditionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
-    Or(
-        ConditionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
+    And(Box<ConditionExpr>),
+    Or(Box<ConditionExpr>),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -267,41 +364,91 @@ pub enum Operator {
 pub enum Cond {
     Func(ConditionFunctionExpression),
     Cmp(ConditionComparisonExpression),
+    // A parenthesized sub expression, e.g. `(#a = :a OR #b = :b) AND #c = :c`.
+    Group(Box<ConditionExpr>),
 }
 
-impl std::string::ToString for Cond {
-    fn to_string(&self) -> String {
+impl Cond {
+    fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
         match self {
-            Self::Func(func) => func.to_string(),
-            Self::Cmp(cmp) => cmp.to_string(),
+            Self::Func(func) => func.render(placeholders),
+            Self::Cmp(cmp) => cmp.render(placeholders),
+            Self::Group(expr) => {
+                let (s, names, values) = expr.render(placeholders);
+                (format!("({})", s), names, values)
+            }
         }
     }
 }
 
-impl super::ToAttrNames for Cond {
-    fn to_attr_names(&self) -> super::AttributeNames {
-        match self {
-            Self::Func(cond) => cond.to_attr_names(),
-            Self::Cmp(cmp) => cmp.to_attr_names(),
-        }
-    }
+// An already built or parsed tree, typed for one table by e.g. `User::condition_from(expr)`.
+#[derive(Debug, Clone)]
+pub struct ConditionFromExpr<T> {
+    pub expr: ConditionExpr,
+    pub _token: std::marker::PhantomData<fn() -> T>,
 }
 
-impl super::IntoAttrValues for Cond {
-    fn into_attr_values(self) -> super::AttributeValues {
-        match self {
-            Self::Func(cond) => cond.into_attr_values(),
-            Self::Cmp(cmp) => cmp.into_attr_values(),
-        }
+impl<T> ConditionBuilder<T> for ConditionFromExpr<T> {
+    fn into_expr(self) -> ConditionExpr {
+        self.expr
     }
 }
 
 pub trait ConditionBuilder<T> {
+    fn into_expr(self) -> ConditionExpr;
+
+    // A value which failed to convert. `build` ignores it, `try_build_with` returns it.
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        None
+    }
+
+    fn try_build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> Result<
+        (
+            ConditionString,
+            super::AttributeNames,
+            super::AttributeValues,
+        ),
+        DeferredConversionError,
+    >
+    where
+        Self: Sized,
+    {
+        match self.conversion_error() {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.build_with(placeholders)),
+        }
+    }
+
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    )
+    where
+        Self: Sized,
+    {
+        self.into_expr().render(placeholders)
+    }
+
     fn build(
         self,
     ) -> (
         ConditionString,
         super::AttributeNames,
         super::AttributeValues,
-    );
+    )
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut super::Placeholders::new())
+    }
 }
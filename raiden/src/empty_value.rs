@@ -0,0 +1,55 @@
+use crate::AttributeValue;
+
+// How an empty string, binary, list, map or set is written, selected with
+// `#[raiden(empty_value = "omit" | "null" | "empty")]` on a struct or a field.
+// It applies to put, transact put and update set, and only to the top-level attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmptyValuePolicy {
+    // Leave the attribute out, or remove it on update. It's read back as an empty value.
+    Omit,
+    // Store `NULL`. It's read back as an empty value.
+    // This is the default, as `&str`, `Vec` and the update set of an empty value were written as `NULL` before.
+    #[default]
+    Null,
+    // Store the empty value as is. Sets can't be empty in DynamoDB, so they are omitted.
+    Empty,
+}
+
+impl EmptyValuePolicy {
+    // Returns the value to write, or `None` if the attribute should be left out.
+    pub fn apply(self, value: AttributeValue) -> Option<AttributeValue> {
+        if !is_empty_value(&value) {
+            return Some(value);
+        }
+        match self {
+            EmptyValuePolicy::Omit => None,
+            EmptyValuePolicy::Null => Some(AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            }),
+            // Empty sets are converted to `AttributeValue::default()`.
+            EmptyValuePolicy::Empty if value == AttributeValue::default() => None,
+            EmptyValuePolicy::Empty => Some(value),
+        }
+    }
+
+    // Reads an omitted attribute as `NULL`, which converts to an empty value.
+    pub fn read(self, value: Option<AttributeValue>) -> Option<AttributeValue> {
+        match (self, value) {
+            (EmptyValuePolicy::Omit, None) => Some(AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            }),
+            (_, value) => value,
+        }
+    }
+}
+
+// Whether `value` is an empty string, binary, list, map or set.
+pub fn is_empty_value(value: &AttributeValue) -> bool {
+    value == &AttributeValue::default()
+        || matches!(&value.s, Some(s) if s.is_empty())
+        || matches!(&value.b, Some(b) if b.is_empty())
+        || matches!(&value.l, Some(l) if l.is_empty())
+        || matches!(&value.m, Some(m) if m.is_empty())
+}
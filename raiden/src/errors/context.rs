@@ -0,0 +1,163 @@
+use crate::{AttributeValue, AttributeValues, FromAttribute, RaidenError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+    GetItem,
+    BatchGetItem,
+    Query,
+    Scan,
+    PutItem,
+    UpdateItem,
+    DeleteItem,
+    BatchWriteItem,
+    TransactWriteItems,
+}
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Operation::GetItem => "GetItem",
+            Operation::BatchGetItem => "BatchGetItem",
+            Operation::Query => "Query",
+            Operation::Scan => "Scan",
+            Operation::PutItem => "PutItem",
+            Operation::UpdateItem => "UpdateItem",
+            Operation::DeleteItem => "DeleteItem",
+            Operation::BatchWriteItem => "BatchWriteItem",
+            Operation::TransactWriteItems => "TransactWriteItems",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Where an error returned by a generated builder happened.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorContext {
+    pub operation: Operation,
+    // Resolved with the client's prefix and suffix. `None` when the operation spans tables.
+    pub table_name: Option<String>,
+    pub key: Option<AttributeValues>,
+    pub attempts: usize,
+    // Only available when DynamoDB's response reached raiden unparsed.
+    pub request_id: Option<String>,
+}
+
+impl ErrorContext {
+    pub fn new(operation: Operation) -> Self {
+        Self {
+            operation,
+            table_name: None,
+            key: None,
+            attempts: 0,
+            request_id: None,
+        }
+    }
+
+    pub fn with_table_name(mut self, table_name: impl Into<String>) -> Self {
+        self.table_name = Some(table_name.into());
+        self
+    }
+
+    pub fn with_key(mut self, key: AttributeValues) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        if let Some(table_name) = &self.table_name {
+            write!(f, " on `{}`", table_name)?;
+        }
+        if let Some(key) = &self.key {
+            // Rendered as JSON, e.g. `{"id":"id0"}`.
+            let key = serde_json::Value::from_attr(Some(AttributeValue {
+                m: Some(key.clone()),
+                ..AttributeValue::default()
+            }));
+            if let Ok(key) = key {
+                write!(f, " with key {}", key)?;
+            }
+        }
+        write!(f, ", attempt {}", self.attempts)?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, ", request id {}", request_id)?;
+        }
+        Ok(())
+    }
+}
+
+// An error returned by `run_with_context`, with where it happened.
+// It derefs to the error itself, and `RaidenError::from` drops the context.
+#[derive(Debug, PartialEq)]
+pub struct ErrorWithContext {
+    pub error: RaidenError,
+    pub context: ErrorContext,
+}
+
+impl std::ops::Deref for ErrorWithContext {
+    type Target = RaidenError;
+
+    fn deref(&self) -> &RaidenError {
+        &self.error
+    }
+}
+
+impl From<ErrorWithContext> for RaidenError {
+    fn from(error: ErrorWithContext) -> Self {
+        error.error
+    }
+}
+
+impl std::fmt::Display for ErrorWithContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.error, self.context)
+    }
+}
+
+impl std::error::Error for ErrorWithContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+// Tracks the attempts of the request an operation sent last, e.g. the page of a query,
+// and the retries of all its requests.
+#[derive(Debug, Default)]
+pub struct Attempts {
+    current: std::sync::atomic::AtomicUsize,
+    retries: std::sync::atomic::AtomicUsize,
+}
+
+impl Attempts {
+    // Called before each request, with the number of its attempt (1 origin).
+    pub fn start(&self, attempt: usize) {
+        self.current
+            .store(attempt, std::sync::atomic::Ordering::SeqCst);
+        if attempt > 1 {
+            self.retries
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    // Attempts of the request sent last, counting its retries but not earlier pages.
+    pub fn get(&self) -> usize {
+        self.current.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    // Retries of all requests sent so far.
+    pub fn retries(&self) -> usize {
+        self.retries.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
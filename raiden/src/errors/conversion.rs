@@ -0,0 +1,264 @@
+use crate::{AttributeType, AttributeValue};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConversionErrorKind {
+    ValueIsNone,
+    TypeMismatch,
+    ParseInt,
+    ParseNumber,
+    ParseDateTime,
+    Serde,
+}
+
+impl std::fmt::Display for ConversionErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionErrorKind::ValueIsNone => write!(f, "Value is none"),
+            ConversionErrorKind::TypeMismatch => write!(f, "Type mismatch"),
+            ConversionErrorKind::ParseInt => write!(f, "Parsing error of integer"),
+            ConversionErrorKind::ParseNumber => write!(f, "Parsing error of number"),
+            ConversionErrorKind::ParseDateTime => write!(f, "Parsing error of date time"),
+            ConversionErrorKind::Serde => write!(f, "Serde conversion error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+// Location of a nested attribute, e.g. `address.lines[2]`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AttributePath(pub Vec<PathSegment>);
+
+impl AttributePath {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Display for AttributePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                PathSegment::Key(key) => write!(f, ".{}", key)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn attribute_type_of(value: &AttributeValue) -> Option<AttributeType> {
+    if value.s.is_some() {
+        Some(AttributeType::S)
+    } else if value.n.is_some() {
+        Some(AttributeType::N)
+    } else if value.b.is_some() {
+        Some(AttributeType::B)
+    } else if value.bool.is_some() {
+        Some(AttributeType::BOOL)
+    } else if value.null.is_some() {
+        Some(AttributeType::NULL)
+    } else if value.l.is_some() {
+        Some(AttributeType::L)
+    } else if value.m.is_some() {
+        Some(AttributeType::M)
+    } else if value.ss.is_some() {
+        Some(AttributeType::SS)
+    } else if value.ns.is_some() {
+        Some(AttributeType::NS)
+    } else if value.bs.is_some() {
+        Some(AttributeType::BS)
+    } else {
+        None
+    }
+}
+
+type Cause = std::sync::Arc<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub struct ConversionError {
+    kind: ConversionErrorKind,
+    path: AttributePath,
+    expected: Option<AttributeType>,
+    actual: Option<AttributeType>,
+    cause: Option<Cause>,
+}
+
+impl ConversionError {
+    pub fn new(kind: ConversionErrorKind) -> Self {
+        Self {
+            kind,
+            path: AttributePath::default(),
+            expected: None,
+            actual: None,
+            cause: None,
+        }
+    }
+
+    // The attribute is absent.
+    pub fn missing(expected: AttributeType) -> Self {
+        Self::new(ConversionErrorKind::ValueIsNone).with_expected(expected)
+    }
+
+    // The attribute holds another type than `expected`.
+    pub fn mismatch(expected: AttributeType, actual: &AttributeValue) -> Self {
+        let mut error = Self::new(ConversionErrorKind::TypeMismatch).with_expected(expected);
+        error.actual = attribute_type_of(actual);
+        error
+    }
+
+    pub fn with_expected(mut self, expected: AttributeType) -> Self {
+        self.expected = Some(expected);
+        self
+    }
+
+    pub fn with_cause(mut self, cause: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.cause = Some(std::sync::Arc::new(cause));
+        self
+    }
+
+    // Called while the error bubbles up, so segments are prepended.
+    pub fn at_key(mut self, key: impl Into<String>) -> Self {
+        self.path.0.insert(0, PathSegment::Key(key.into()));
+        self
+    }
+
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.0.insert(0, PathSegment::Index(index));
+        self
+    }
+
+    pub fn kind(&self) -> &ConversionErrorKind {
+        &self.kind
+    }
+
+    pub fn path(&self) -> &AttributePath {
+        &self.path
+    }
+
+    pub fn expected(&self) -> Option<&AttributeType> {
+        self.expected.as_ref()
+    }
+
+    pub fn actual(&self) -> Option<&AttributeType> {
+        self.actual.as_ref()
+    }
+}
+
+impl PartialEq for ConversionError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.path == other.path
+            && self.expected == other.expected
+            && self.actual == other.actual
+            && self.cause.as_ref().map(|c| c.to_string())
+                == other.cause.as_ref().map(|c| c.to_string())
+    }
+}
+
+// Lets custom `FromAttribute` impls keep returning a bare kind, e.g. `Err(ConversionErrorKind::ValueIsNone.into())`.
+impl From<ConversionErrorKind> for ConversionError {
+    fn from(kind: ConversionErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+// Compares the kind only, in place of matching on the former enum variants.
+impl PartialEq<ConversionErrorKind> for ConversionError {
+    fn eq(&self, kind: &ConversionErrorKind) -> bool {
+        &self.kind == kind
+    }
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if !self.path.is_empty() {
+            write!(f, " at `{}`", self.path)?;
+        }
+        match (&self.expected, &self.actual) {
+            (Some(expected), Some(actual)) => {
+                write!(f, " (expected {}, found {})", expected, actual)
+            }
+            (Some(expected), None) => write!(f, " (expected {})", expected),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.cause
+            .as_ref()
+            .map(|cause| cause.as_ref() as &(dyn std::error::Error + 'static))
+    }
+}
+
+// A value which failed to convert while a request was being built.
+// Builders keep the first one and return it from `run` as `RaidenError::AttributeConvertError`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeferredConversionError {
+    pub attr_name: String,
+    pub source: ConversionError,
+}
+
+impl DeferredConversionError {
+    // The converted value, or NULL after keeping the error in `slot` unless it already holds one.
+    pub fn collect(
+        slot: &mut Option<DeferredConversionError>,
+        attr_name: &str,
+        value: Result<AttributeValue, ConversionError>,
+    ) -> AttributeValue {
+        value.unwrap_or_else(|source| {
+            if slot.is_none() {
+                *slot = Some(DeferredConversionError {
+                    attr_name: attr_name.to_owned(),
+                    source,
+                });
+            }
+            AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            }
+        })
+    }
+}
+
+impl From<DeferredConversionError> for crate::RaidenError {
+    fn from(error: DeferredConversionError) -> Self {
+        crate::RaidenError::AttributeConvertError {
+            attr_name: error.attr_name,
+            source: error.source,
+        }
+    }
+}
+
+// Takes one representation out of `value`, reporting a missing attribute or a type mismatch.
+pub(crate) fn take_attr<T>(
+    value: Option<AttributeValue>,
+    expected: AttributeType,
+    take: impl FnOnce(&mut AttributeValue) -> Option<T>,
+) -> Result<T, ConversionError> {
+    let mut value = value.ok_or_else(|| ConversionError::missing(expected.clone()))?;
+    match take(&mut value) {
+        Some(taken) => Ok(taken),
+        None => Err(ConversionError::mismatch(expected, &value)),
+    }
+}
+
+pub(crate) fn parse_number<T>(n: &str, kind: ConversionErrorKind) -> Result<T, ConversionError>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    n.parse().map_err(|e| {
+        ConversionError::new(kind)
+            .with_expected(AttributeType::N)
+            .with_cause(e)
+    })
+}
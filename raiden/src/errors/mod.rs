@@ -1,9 +1,19 @@
+mod context;
+mod conversion;
 mod transaction;
+mod unknown;
 
 use crate::*;
 use thiserror::Error;
 
+pub use context::{Attempts, ErrorContext, ErrorWithContext, Operation};
+pub use conversion::{
+    attribute_type_of, AttributePath, ConversionError, ConversionErrorKind,
+    DeferredConversionError, PathSegment,
+};
+pub(crate) use conversion::{parse_number, take_attr};
 pub use transaction::*;
+use unknown::classify_unknown;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum RaidenError {
@@ -35,11 +45,23 @@ pub enum RaidenError {
     Validation(String),
     #[error("`{0}`")]
     ParseError(String),
+    #[error("`{0}`")]
+    ThrottlingException(String),
+    #[error("`{0}`")]
+    AccessDenied(String),
+    #[error("`{0}`")]
+    UnrecognizedClient(String),
+    #[error("`{0}`")]
+    ExpiredToken(String),
+    #[error("`{0}`")]
+    ServiceUnavailable(String),
     #[error("unknown error")]
     Unknown(crate::request::BufferedHttpResponse),
     #[error("`transaction canceled error {reasons}`")]
     TransactionCanceled {
         reasons: RaidenTransactionCancellationReasons,
+        // Empty if DynamoDB's response only had the reasons in its message.
+        failed_items: Vec<RaidenTransactionFailedItem>,
     },
     #[error("`{0}`")]
     TransactionInProgress(String),
@@ -49,10 +71,59 @@ pub enum RaidenError {
     Blocking,
     #[error("next_token decode error")]
     NextTokenDecodeError,
+    #[error("`{0}`")]
+    MiddlewareError(String),
     #[error("attribute {attr_name:?} convert error")]
-    AttributeConvertError { attr_name: String },
+    AttributeConvertError {
+        attr_name: String,
+        source: ConversionError,
+    },
     #[error("attribute {attr_name:?} value not found")]
     AttributeValueNotFoundError { attr_name: String },
+    #[error("expression placeholder {placeholder:?} is bound to different values")]
+    ExpressionConflict { placeholder: String },
+}
+
+impl RaidenError {
+    // Attaches where the error happened. The request id is taken from the response if the context has none.
+    pub fn with_context(self, mut context: ErrorContext) -> ErrorWithContext {
+        if context.request_id.is_none() {
+            if let RaidenError::Unknown(res) = &self {
+                context.request_id = res.headers.get("x-amzn-requestid").cloned();
+            }
+        }
+        ErrorWithContext {
+            error: self,
+            context,
+        }
+    }
+
+    // Items which canceled a transaction. Empty for any other error.
+    pub fn failed_items(&self) -> &[RaidenTransactionFailedItem] {
+        match self {
+            RaidenError::TransactionCanceled { failed_items, .. } => failed_items,
+            _ => &[],
+        }
+    }
+
+    // DynamoDB rejected the request because of its rate, so it may succeed after backing off.
+    pub fn is_throttling(&self) -> bool {
+        matches!(
+            self,
+            RaidenError::ProvisionedThroughputExceeded(_)
+                | RaidenError::RequestLimitExceeded(_)
+                | RaidenError::ThrottlingException(_)
+        )
+    }
+
+    // Throttling or a transient server side failure. Other errors fail the same way when retried.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RaidenError::InternalServerError(_) | RaidenError::ServiceUnavailable(_) => true,
+            RaidenError::Unknown(res) => res.status.is_server_error(),
+            error => error.is_throttling(),
+        }
+    }
 }
 
 impl From<RusotoError<BatchGetItemError>> for RaidenError {
@@ -74,7 +145,7 @@ impl From<RusotoError<BatchGetItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -102,7 +173,7 @@ impl From<RusotoError<BatchWriteItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -123,7 +194,7 @@ impl From<RusotoError<GetItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -144,7 +215,7 @@ impl From<RusotoError<QueryError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -165,7 +236,7 @@ impl From<RusotoError<ScanError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -193,7 +264,7 @@ impl From<RusotoError<PutItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -223,7 +294,7 @@ impl From<RusotoError<UpdateItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -253,7 +324,7 @@ impl From<RusotoError<DeleteItemError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -280,7 +351,10 @@ impl From<RusotoError<TransactWriteItemsError>> for RaidenError {
                 }
                 TransactWriteItemsError::TransactionCanceled(msg) => {
                     let reasons = RaidenTransactionCancellationReasons::from_str(&msg);
-                    RaidenError::TransactionCanceled { reasons }
+                    RaidenError::TransactionCanceled {
+                        reasons,
+                        failed_items: vec![],
+                    }
                 }
                 TransactWriteItemsError::TransactionInProgress(msg) => {
                     RaidenError::TransactionInProgress(msg)
@@ -290,7 +364,7 @@ impl From<RusotoError<TransactWriteItemsError>> for RaidenError {
             RusotoError::Credentials(e) => RaidenError::Credentials(e),
             RusotoError::Validation(msg) => RaidenError::Validation(msg),
             RusotoError::ParseError(msg) => RaidenError::ParseError(msg),
-            RusotoError::Unknown(res) => RaidenError::Unknown(res),
+            RusotoError::Unknown(res) => classify_unknown(res),
             RusotoError::Blocking => RaidenError::Blocking,
         }
     }
@@ -1,7 +1,10 @@
 use std::fmt;
 
+use serde::Deserialize;
 use thiserror::Error;
 
+use crate::AttributeValues;
+
 const TRANSACTION_CANCELLED_MESSAGE_PREFIX: &str =
     "Transaction cancelled, please refer cancellation reasons for specific reasons";
 
@@ -34,6 +37,34 @@ impl RaidenTransactionCancellationReasons {
         )
     }
 
+    // Parses the `CancellationReasons` of a `TransactionCanceledException` response body,
+    // along with an entry per canceled item. Returns `None` if `body` has no reasons.
+    pub fn from_response_body(body: &[u8]) -> Option<(Self, Vec<RaidenTransactionFailedItem>)> {
+        let response: CancellationReasonsResponse = serde_json::from_slice(body).ok()?;
+        let mut failed_items = vec![];
+        let reasons = response
+            .cancellation_reasons
+            .into_iter()
+            .enumerate()
+            .map(|(index, reason)| match reason.code.as_deref() {
+                None | Some("None") => None,
+                Some(code) => {
+                    let code = RaidenTransactionCancellationReason::from_str(code);
+                    failed_items.push(RaidenTransactionFailedItem {
+                        index,
+                        table_name: None,
+                        key: None,
+                        reason: code.clone(),
+                        message: reason.message,
+                        item: reason.item,
+                    });
+                    Some(code)
+                }
+            })
+            .collect();
+        Some((RaidenTransactionCancellationReasons(reasons), failed_items))
+    }
+
     fn has_error(&self, r: RaidenTransactionCancellationReason) -> bool {
         self.0
             .iter()
@@ -80,6 +111,35 @@ impl fmt::Display for RaidenTransactionCancellationReasons {
     }
 }
 
+#[derive(Deserialize)]
+struct CancellationReasonsResponse {
+    #[serde(rename = "CancellationReasons")]
+    cancellation_reasons: Vec<CancellationReasonResponse>,
+}
+
+#[derive(Deserialize)]
+struct CancellationReasonResponse {
+    #[serde(rename = "Code")]
+    code: Option<String>,
+    #[serde(rename = "Message")]
+    message: Option<String>,
+    #[serde(rename = "Item")]
+    item: Option<AttributeValues>,
+}
+
+// An item which canceled a transaction, tied back to the `WriteTx` call that added it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaidenTransactionFailedItem {
+    // Position of the item in the transaction, in the order it was added.
+    pub index: usize,
+    pub table_name: Option<String>,
+    pub key: Option<AttributeValues>,
+    pub reason: RaidenTransactionCancellationReason,
+    pub message: Option<String>,
+    // The item before the transaction, returned with `ReturnValuesOnConditionCheckFailure` set to `ALL_OLD`.
+    pub item: Option<AttributeValues>,
+}
+
 #[derive(Error, Clone, Debug, PartialEq)]
 pub enum RaidenTransactionCancellationReason {
     #[error("Unknown")]
@@ -117,7 +177,10 @@ impl RaidenTransactionCancellationReason {
 
 #[cfg(test)]
 mod tests {
-    use crate::{RaidenTransactionCancellationReason, RaidenTransactionCancellationReasons};
+    use crate::{
+        IntoAttribute, RaidenTransactionCancellationReason, RaidenTransactionCancellationReasons,
+        RaidenTransactionFailedItem,
+    };
 
     #[test]
     fn parse_message_single() {
@@ -172,6 +235,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_response_body() {
+        let body = br#"{
+            "__type": "com.amazonaws.dynamodb.v20120810#TransactionCanceledException",
+            "Message": "Transaction cancelled, please refer cancellation reasons for specific reasons [None, ConditionalCheckFailed]",
+            "CancellationReasons": [
+                { "Code": "None" },
+                {
+                    "Code": "ConditionalCheckFailed",
+                    "Message": "The conditional request failed",
+                    "Item": { "id": { "S": "id1" } }
+                }
+            ]
+        }"#;
+        let (reasons, failed_items) =
+            RaidenTransactionCancellationReasons::from_response_body(body).unwrap();
+
+        assert_eq!(
+            reasons,
+            RaidenTransactionCancellationReasons(vec![
+                None,
+                Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
+            ])
+        );
+        let mut item = std::collections::HashMap::new();
+        item.insert("id".to_owned(), "id1".into_attr());
+        assert_eq!(
+            failed_items,
+            vec![RaidenTransactionFailedItem {
+                index: 1,
+                table_name: None,
+                key: None,
+                reason: RaidenTransactionCancellationReason::ConditionalCheckFailed,
+                message: Some("The conditional request failed".to_owned()),
+                item: Some(item),
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_response_body_without_reasons() {
+        let body = br#"{"__type": "com.amazonaws.dynamodb.v20120810#ResourceNotFoundException"}"#;
+        assert!(RaidenTransactionCancellationReasons::from_response_body(body).is_none());
+    }
+
     #[test]
     fn has_error() {
         let results = RaidenTransactionCancellationReasons(vec![
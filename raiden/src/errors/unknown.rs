@@ -0,0 +1,96 @@
+use super::RaidenError;
+use crate::request::BufferedHttpResponse;
+
+// Error response of DynamoDB which rusoto couldn't map to an operation error, e.g.
+// `{"__type":"com.amazon.coral.availability#ThrottlingException","message":"Rate of requests exceeds the allowed throughput."}`
+#[derive(serde::Deserialize, Default)]
+struct ErrorResponse {
+    #[serde(rename = "__type")]
+    error_type: Option<String>,
+    #[serde(alias = "Message")]
+    message: Option<String>,
+}
+
+pub(crate) fn classify_unknown(res: BufferedHttpResponse) -> RaidenError {
+    match classify(res.status.as_u16(), &res.body) {
+        Some(error) => error,
+        None => RaidenError::Unknown(res),
+    }
+}
+
+fn classify(status: u16, body: &[u8]) -> Option<RaidenError> {
+    let res: ErrorResponse = serde_json::from_slice(body).unwrap_or_default();
+    let message = res.message.unwrap_or_default();
+    // The type is prefixed with its namespace, which differs between services.
+    let error_type = res.error_type.as_deref().and_then(|t| t.rsplit('#').next());
+    let error = match error_type {
+        Some("ThrottlingException") => RaidenError::ThrottlingException(message),
+        Some("ProvisionedThroughputExceededException") => {
+            RaidenError::ProvisionedThroughputExceeded(message)
+        }
+        Some("RequestLimitExceeded") => RaidenError::RequestLimitExceeded(message),
+        Some("AccessDeniedException") => RaidenError::AccessDenied(message),
+        Some("UnrecognizedClientException") => RaidenError::UnrecognizedClient(message),
+        Some("ExpiredTokenException") => RaidenError::ExpiredToken(message),
+        Some("ServiceUnavailable") | Some("ServiceUnavailableException") => {
+            RaidenError::ServiceUnavailable(message)
+        }
+        Some("InternalServerError") | Some("InternalFailure") => {
+            RaidenError::InternalServerError(message)
+        }
+        Some("ValidationException") => RaidenError::Validation(message),
+        _ if status == 503 => RaidenError::ServiceUnavailable(message),
+        _ => return None,
+    };
+    Some(error)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_classify_throttling() {
+        let body = r#"{"__type":"com.amazon.coral.availability#ThrottlingException","message":"Rate exceeded"}"#;
+        assert_eq!(
+            classify(400, body.as_bytes()),
+            Some(RaidenError::ThrottlingException("Rate exceeded".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_classify_client_errors() {
+        let body = r#"{"__type":"com.amazon.coral.service#UnrecognizedClientException","Message":"The security token included in the request is invalid."}"#;
+        assert_eq!(
+            classify(400, body.as_bytes()),
+            Some(RaidenError::UnrecognizedClient(
+                "The security token included in the request is invalid.".to_owned()
+            ))
+        );
+        let body = r#"{"__type":"com.amazon.coral.service#ExpiredTokenException"}"#;
+        assert_eq!(
+            classify(400, body.as_bytes()),
+            Some(RaidenError::ExpiredToken("".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_classify_unexpected_response() {
+        assert_eq!(
+            classify(503, b"<html></html>"),
+            Some(RaidenError::ServiceUnavailable("".to_owned()))
+        );
+        assert_eq!(classify(400, b"<html></html>"), None);
+        assert_eq!(
+            classify(
+                400,
+                br#"{"__type":"com.amazon.coral.service#SomethingNew"}"#
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use super::ExprError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    // `#name`
+    Name(String),
+    // `:value`
+    Value(String),
+    // Bare attribute names, function names and keywords.
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Plus,
+    Minus,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Token::Name(s) | Token::Value(s) | Token::Ident(s) => write!(f, "{}", s),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::Comma => write!(f, ","),
+            Token::Eq => write!(f, "="),
+            Token::Ne => write!(f, "<>"),
+            Token::Lt => write!(f, "<"),
+            Token::Le => write!(f, "<="),
+            Token::Gt => write!(f, ">"),
+            Token::Ge => write!(f, ">="),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+        }
+    }
+}
+
+impl Token {
+    pub(crate) fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Ident(s) if s.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+pub(crate) fn tokenize(expression: &str) -> Result<Vec<Token>, ExprError> {
+    let mut tokens = vec![];
+    let mut chars = expression.char_indices().peekable();
+    while let Some((pos, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '=' => Token::Eq,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '<' => match chars.peek() {
+                Some((_, '>')) => {
+                    chars.next();
+                    Token::Ne
+                }
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::Le
+                }
+                _ => Token::Lt,
+            },
+            '>' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::Ge
+                }
+                _ => Token::Gt,
+            },
+            '#' | ':' => {
+                let mut s = c.to_string();
+                while let Some((_, c)) = chars.peek() {
+                    if !is_ident_char(*c) {
+                        break;
+                    }
+                    s.push(*c);
+                    chars.next();
+                }
+                if s.len() == 1 {
+                    return Err(ExprError::UnexpectedChar(c, pos));
+                }
+                if c == '#' {
+                    Token::Name(s)
+                } else {
+                    Token::Value(s)
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut s = c.to_string();
+                while let Some((_, c)) = chars.peek() {
+                    if !is_ident_char(*c) {
+                        break;
+                    }
+                    s.push(*c);
+                    chars.next();
+                }
+                Token::Ident(s)
+            }
+            '.' | '[' => return Err(ExprError::Unsupported("document path".to_owned())),
+            c => return Err(ExprError::UnexpectedChar(c, pos)),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
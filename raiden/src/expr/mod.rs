@@ -0,0 +1,417 @@
+// Parses textual DynamoDB expressions into the trees produced by raiden's builders.
+// ref: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html
+mod lexer;
+mod parser;
+
+use parser::{Comparator, Leaf, Node, Operand, Parser};
+use thiserror::Error;
+
+use crate::condition::{
+    Cond, ConditionComparisonExpression, ConditionExpr, ConditionFunctionExpression, Operator,
+};
+use crate::filter_expression::{FilterExpr, FilterExpressionOperator, FilterExpressionTypes};
+use crate::key_condition::{KeyConditionExpr, KeyConditionOperator, KeyConditionTypes};
+use crate::update_expression::UpdateExpr;
+use crate::{AttrOrValue, AttributeNames, AttributeType, AttributeValue, AttributeValues};
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ExprError {
+    #[error("unexpected character {0:?} at {1}")]
+    UnexpectedChar(char, usize),
+    #[error("unexpected token `{0}`")]
+    UnexpectedToken(String),
+    #[error("unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("expression attribute name {0:?} is not defined")]
+    UnknownName(String),
+    #[error("expression attribute value {0:?} is not defined")]
+    UnknownValue(String),
+    #[error("{0} clause appears more than once")]
+    DuplicateClause(String),
+    #[error("{0} is not supported")]
+    Unsupported(String),
+}
+
+// `attribute_exists(#a) AND #b > :v`
+pub fn parse_condition(
+    expression: &str,
+    names: &AttributeNames,
+    values: &AttributeValues,
+) -> Result<ConditionExpr, ExprError> {
+    let mut parser = Parser::new(expression, names, values)?;
+    let node = parser.condition()?;
+    parser.finish()?;
+    lower_condition(node)
+}
+
+// Filter trees are a flat chain of `attr op value`, so explicit grouping on the left and `NOT` are rejected.
+pub fn parse_filter(
+    expression: &str,
+    names: &AttributeNames,
+    values: &AttributeValues,
+) -> Result<FilterExpr, ExprError> {
+    let mut parser = Parser::new(expression, names, values)?;
+    let node = parser.condition()?;
+    parser.finish()?;
+    lower_filter(node)
+}
+
+// `#pk = :pk AND begins_with(#sk, :prefix)`
+pub fn parse_key_condition(
+    expression: &str,
+    names: &AttributeNames,
+    values: &AttributeValues,
+) -> Result<KeyConditionExpr, ExprError> {
+    let mut parser = Parser::new(expression, names, values)?;
+    let node = parser.condition()?;
+    parser.finish()?;
+    lower_key_condition(node)
+}
+
+// `SET #a = :a, #b = #b + :one REMOVE #c`
+pub fn parse_update(
+    expression: &str,
+    names: &AttributeNames,
+    values: &AttributeValues,
+) -> Result<UpdateExpr, ExprError> {
+    Parser::new(expression, names, values)?.update()
+}
+
+// Returns the attribute names in order, e.g. `#id, name` => `["id", "name"]`.
+pub fn parse_projection(
+    expression: &str,
+    names: &AttributeNames,
+) -> Result<Vec<String>, ExprError> {
+    let values = AttributeValues::new();
+    Parser::new(expression, names, &values)?.projection()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Logical {
+    And,
+    Or,
+}
+
+fn strip_parens(node: Node) -> Node {
+    match node {
+        Node::Paren(inner) => strip_parens(*inner),
+        node => node,
+    }
+}
+
+// Splits a node into its left hand side and the optional `(logical, right)` rest.
+// Chains of the same operator are parsed left associative, so they are rotated into the right nested shape builders produce.
+fn split(node: Node) -> (Node, Option<(Logical, Node)>) {
+    let (mut left, logical, mut right) = match strip_parens(node) {
+        Node::And(l, r) => (*l, Logical::And, *r),
+        Node::Or(l, r) => (*l, Logical::Or, *r),
+        node => return (node, None),
+    };
+    loop {
+        left = match (left, logical) {
+            (Node::And(l, r), Logical::And) => {
+                right = Node::And(r, Box::new(right));
+                *l
+            }
+            (Node::Or(l, r), Logical::Or) => {
+                right = Node::Or(r, Box::new(right));
+                *l
+            }
+            (left, _) => return (left, Some((logical, right))),
+        };
+    }
+}
+
+fn lower_condition(node: Node) -> Result<ConditionExpr, ExprError> {
+    let (left, right) = split(node);
+    let (not, cond) = match strip_parens(left) {
+        Node::Not(inner) => match strip_parens(*inner) {
+            Node::Leaf(leaf) => (true, lower_condition_leaf(leaf)?),
+            inner => (true, Cond::Group(Box::new(lower_condition(inner)?))),
+        },
+        Node::Leaf(leaf) => (false, lower_condition_leaf(leaf)?),
+        left => (false, Cond::Group(Box::new(lower_condition(left)?))),
+    };
+    let operator = match right {
+        Some((Logical::And, right)) => Some(Operator::And(Box::new(lower_condition(right)?))),
+        Some((Logical::Or, right)) => Some(Operator::Or(Box::new(lower_condition(right)?))),
+        None => None,
+    };
+    Ok(ConditionExpr {
+        not,
+        cond,
+        operator,
+    })
+}
+
+fn into_attr_or_value(operand: Operand) -> Result<AttrOrValue, ExprError> {
+    match operand {
+        Operand::Path(path) => Ok(AttrOrValue::Attr(path)),
+        Operand::Value(value) => Ok(AttrOrValue::Value(value)),
+        Operand::Size(_) => Err(ExprError::Unsupported(
+            "size function in conditions".to_owned(),
+        )),
+    }
+}
+
+fn into_path(operand: Operand) -> Result<String, ExprError> {
+    match operand {
+        Operand::Path(path) => Ok(path),
+        _ => Err(ExprError::Unsupported("non attribute operand".to_owned())),
+    }
+}
+
+fn into_value(operand: Operand) -> Result<AttributeValue, ExprError> {
+    match operand {
+        Operand::Value(value) => Ok(value),
+        _ => Err(ExprError::Unsupported("non value operand".to_owned())),
+    }
+}
+
+fn into_string(operand: Operand) -> Result<String, ExprError> {
+    into_value(operand)?
+        .s
+        .ok_or_else(|| ExprError::Unsupported("non string argument".to_owned()))
+}
+
+fn into_attribute_type(operand: Operand) -> Result<AttributeType, ExprError> {
+    let attribute_type = into_string(operand)?;
+    Ok(match attribute_type.as_str() {
+        "S" => AttributeType::S,
+        "SS" => AttributeType::SS,
+        "N" => AttributeType::N,
+        "NS" => AttributeType::NS,
+        "B" => AttributeType::B,
+        "BS" => AttributeType::BS,
+        "BOOL" => AttributeType::BOOL,
+        "NULL" => AttributeType::NULL,
+        "L" => AttributeType::L,
+        "M" => AttributeType::M,
+        _ => {
+            return Err(ExprError::Unsupported(format!(
+                "attribute type {:?}",
+                attribute_type
+            )))
+        }
+    })
+}
+
+// Takes exactly `N` arguments of a function call.
+fn function_args<const N: usize>(
+    name: &str,
+    args: Vec<Operand>,
+) -> Result<[Operand; N], ExprError> {
+    let len = args.len();
+    std::convert::TryInto::try_into(args)
+        .map_err(|_| ExprError::Unsupported(format!("{} with {} arguments", name, len)))
+}
+
+fn lower_condition_leaf(leaf: Leaf) -> Result<Cond, ExprError> {
+    use ConditionComparisonExpression as Cmp;
+    use ConditionFunctionExpression as Func;
+    Ok(match leaf {
+        Leaf::Compare(left, comparator, right) => {
+            let left = into_attr_or_value(left)?;
+            let right = into_attr_or_value(right)?;
+            Cond::Cmp(match comparator {
+                Comparator::Eq => Cmp::Eq(left, right),
+                Comparator::Ne => Cmp::Ne(left, right),
+                Comparator::Lt => Cmp::Lt(left, right),
+                Comparator::Le => Cmp::Le(left, right),
+                Comparator::Gt => Cmp::Gt(left, right),
+                Comparator::Ge => Cmp::Ge(left, right),
+            })
+        }
+        Leaf::Between(attr, low, high) => Cond::Cmp(Cmp::Between(
+            into_attr_or_value(attr)?,
+            into_attr_or_value(low)?,
+            into_attr_or_value(high)?,
+        )),
+        Leaf::Function(name, args) => Cond::Func(match name.as_str() {
+            "attribute_exists" => {
+                let [path] = function_args(&name, args)?;
+                Func::AttributeExists(into_path(path)?)
+            }
+            "attribute_not_exists" => {
+                let [path] = function_args(&name, args)?;
+                Func::AttributeNotExists(into_path(path)?)
+            }
+            "attribute_type" => {
+                let [path, t] = function_args(&name, args)?;
+                Func::AttributeType(into_path(path)?, into_attribute_type(t)?)
+            }
+            "begins_with" => {
+                let [path, s] = function_args(&name, args)?;
+                Func::BeginsWith(into_path(path)?, into_string(s)?)
+            }
+            "contains" => {
+                let [path, s] = function_args(&name, args)?;
+                Func::Contains(into_path(path)?, into_string(s)?)
+            }
+            _ => return Err(ExprError::Unsupported(format!("{} function", name))),
+        }),
+    })
+}
+
+// Left hand side of a filter or key condition, e.g. `#a` or `size(#a)`.
+fn into_filter_attr(operand: Operand) -> Result<(String, bool), ExprError> {
+    match operand {
+        Operand::Path(path) => Ok((path, false)),
+        Operand::Size(path) => Ok((path, true)),
+        Operand::Value(_) => Err(ExprError::Unsupported(
+            "value on the left hand side".to_owned(),
+        )),
+    }
+}
+
+fn lower_filter(node: Node) -> Result<FilterExpr, ExprError> {
+    let (left, right) = split(node);
+    let leaf = match strip_parens(left) {
+        Node::Leaf(leaf) => leaf,
+        Node::Not(_) => {
+            return Err(ExprError::Unsupported(
+                "NOT in filter expressions".to_owned(),
+            ))
+        }
+        _ => {
+            return Err(ExprError::Unsupported(
+                "grouped left operand in filter expressions".to_owned(),
+            ))
+        }
+    };
+    let (attr, is_size, cond) = lower_filter_leaf(leaf)?;
+    let operator = match right {
+        Some((Logical::And, right)) => Some(FilterExpressionOperator::And(Box::new(lower_filter(
+            right,
+        )?))),
+        Some((Logical::Or, right)) => {
+            Some(FilterExpressionOperator::Or(Box::new(lower_filter(right)?)))
+        }
+        None => None,
+    };
+    Ok(FilterExpr {
+        attr,
+        is_size,
+        cond,
+        operator,
+    })
+}
+
+fn lower_filter_leaf(leaf: Leaf) -> Result<(String, bool, FilterExpressionTypes), ExprError> {
+    use FilterExpressionTypes as Types;
+    Ok(match leaf {
+        Leaf::Compare(left, comparator, right) => {
+            let (attr, is_size) = into_filter_attr(left)?;
+            let value = into_value(right)?;
+            let cond = match comparator {
+                Comparator::Eq => Types::Eq(value),
+                Comparator::Ne => Types::Not(value),
+                Comparator::Lt => Types::Lt(value),
+                Comparator::Le => Types::Le(value),
+                Comparator::Gt => Types::Gt(value),
+                Comparator::Ge => Types::Ge(value),
+            };
+            (attr, is_size, cond)
+        }
+        Leaf::Between(attr, low, high) => {
+            let (attr, is_size) = into_filter_attr(attr)?;
+            (
+                attr,
+                is_size,
+                Types::Between(into_value(low)?, into_value(high)?),
+            )
+        }
+        Leaf::Function(name, args) => {
+            let cond = match name.as_str() {
+                "attribute_exists" => {
+                    let [path] = function_args(&name, args)?;
+                    (into_path(path)?, Types::AttributeExists())
+                }
+                "attribute_not_exists" => {
+                    let [path] = function_args(&name, args)?;
+                    (into_path(path)?, Types::AttributeNotExists())
+                }
+                "attribute_type" => {
+                    let [path, t] = function_args(&name, args)?;
+                    (
+                        into_path(path)?,
+                        Types::AttributeType(into_attribute_type(t)?),
+                    )
+                }
+                "begins_with" => {
+                    let [path, v] = function_args(&name, args)?;
+                    (into_path(path)?, Types::BeginsWith(into_value(v)?))
+                }
+                "contains" => {
+                    let [path, v] = function_args(&name, args)?;
+                    (into_path(path)?, Types::Contains(into_value(v)?))
+                }
+                _ => return Err(ExprError::Unsupported(format!("{} function", name))),
+            };
+            (cond.0, false, cond.1)
+        }
+    })
+}
+
+fn lower_key_condition(node: Node) -> Result<KeyConditionExpr, ExprError> {
+    let (left, right) = split(node);
+    let leaf = match strip_parens(left) {
+        Node::Leaf(leaf) => leaf,
+        Node::Not(_) => return Err(ExprError::Unsupported("NOT in key conditions".to_owned())),
+        _ => {
+            return Err(ExprError::Unsupported(
+                "grouped left operand in key conditions".to_owned(),
+            ))
+        }
+    };
+    let (attr, cond) = lower_key_condition_leaf(leaf)?;
+    let operator = match right {
+        Some((Logical::And, right)) => Some(KeyConditionOperator::And(Box::new(
+            lower_key_condition(right)?,
+        ))),
+        Some((Logical::Or, _)) => {
+            return Err(ExprError::Unsupported("OR in key conditions".to_owned()))
+        }
+        None => None,
+    };
+    Ok(KeyConditionExpr {
+        attr,
+        cond,
+        operator,
+    })
+}
+
+fn lower_key_condition_leaf(leaf: Leaf) -> Result<(String, KeyConditionTypes), ExprError> {
+    use KeyConditionTypes as Types;
+    Ok(match leaf {
+        Leaf::Compare(left, comparator, right) => {
+            let attr = into_path(left)?;
+            let value = into_value(right)?;
+            let cond = match comparator {
+                Comparator::Eq => Types::Eq(value),
+                Comparator::Lt => Types::Lt(value),
+                Comparator::Le => Types::Le(value),
+                Comparator::Gt => Types::Gt(value),
+                Comparator::Ge => Types::Ge(value),
+                Comparator::Ne => {
+                    return Err(ExprError::Unsupported("<> in key conditions".to_owned()))
+                }
+            };
+            (attr, cond)
+        }
+        Leaf::Between(attr, low, high) => (
+            into_path(attr)?,
+            Types::Between(into_value(low)?, into_value(high)?),
+        ),
+        Leaf::Function(name, args) if name == "begins_with" => {
+            let [path, v] = function_args(&name, args)?;
+            (into_path(path)?, Types::BeginsWith(into_value(v)?))
+        }
+        Leaf::Function(name, _) => {
+            return Err(ExprError::Unsupported(format!(
+                "{} in key conditions",
+                name
+            )))
+        }
+    })
+}
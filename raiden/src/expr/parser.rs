@@ -0,0 +1,311 @@
+use super::lexer::{tokenize, Token};
+use super::ExprError;
+use crate::update_expression::{ArithmeticOperator, SetAction, UpdateExpr};
+use crate::{AttrOrValue, AttributeNames, AttributeValue, AttributeValues};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Comparator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Operand {
+    Path(String),
+    Value(AttributeValue),
+    Size(String),
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Leaf {
+    Compare(Operand, Comparator, Operand),
+    Between(Operand, Operand, Operand),
+    // Function name in lower case and its arguments.
+    Function(String, Vec<Operand>),
+}
+
+// Boolean tree as written. `Paren` is kept so that lowering can tell explicit grouping from precedence.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    Leaf(Leaf),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Paren(Box<Node>),
+}
+
+pub(crate) struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    names: &'a AttributeNames,
+    values: &'a AttributeValues,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(
+        expression: &str,
+        names: &'a AttributeNames,
+        values: &'a AttributeValues,
+    ) -> Result<Self, ExprError> {
+        Ok(Self {
+            tokens: tokenize(expression)?,
+            pos: 0,
+            names,
+            values,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Result<Token, ExprError> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or(ExprError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExprError> {
+        let token = self.next()?;
+        if token != expected {
+            return Err(ExprError::UnexpectedToken(token.to_string()));
+        }
+        Ok(())
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(token) if token.is_keyword(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_keyword(keyword) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    pub(crate) fn finish(&self) -> Result<(), ExprError> {
+        match self.peek() {
+            Some(token) => Err(ExprError::UnexpectedToken(token.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn resolve_name(&self, token: &Token) -> Result<String, ExprError> {
+        match token {
+            Token::Name(name) => self
+                .names
+                .get(name)
+                .cloned()
+                .ok_or_else(|| ExprError::UnknownName(name.clone())),
+            Token::Ident(ident) => Ok(ident.clone()),
+            token => Err(ExprError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn resolve_value(&self, placeholder: &str) -> Result<AttributeValue, ExprError> {
+        self.values
+            .get(placeholder)
+            .cloned()
+            .ok_or_else(|| ExprError::UnknownValue(placeholder.to_owned()))
+    }
+
+    pub(crate) fn path(&mut self) -> Result<String, ExprError> {
+        let token = self.next()?;
+        self.resolve_name(&token)
+    }
+
+    pub(crate) fn value(&mut self) -> Result<AttributeValue, ExprError> {
+        match self.next()? {
+            Token::Value(placeholder) => self.resolve_value(&placeholder),
+            token => Err(ExprError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, ExprError> {
+        let is_size = self.peek_keyword("size") && self.peek_at(1) == Some(&Token::LParen);
+        if is_size {
+            self.pos += 2;
+            let path = self.path()?;
+            self.expect(Token::RParen)?;
+            return Ok(Operand::Size(path));
+        }
+        match self.next()? {
+            Token::Value(placeholder) => Ok(Operand::Value(self.resolve_value(&placeholder)?)),
+            token => Ok(Operand::Path(self.resolve_name(&token)?)),
+        }
+    }
+
+    pub(crate) fn condition(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.and()?;
+        while self.eat_keyword("OR") {
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.not()?;
+        while self.eat_keyword("AND") {
+            node = Node::And(Box::new(node), Box::new(self.not()?));
+        }
+        Ok(node)
+    }
+
+    fn not(&mut self) -> Result<Node, ExprError> {
+        if self.eat_keyword("NOT") {
+            return Ok(Node::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Node, ExprError> {
+        if self.peek() == Some(&Token::LParen) {
+            self.pos += 1;
+            let node = self.condition()?;
+            self.expect(Token::RParen)?;
+            return Ok(Node::Paren(Box::new(node)));
+        }
+
+        let is_function = matches!(self.peek(), Some(Token::Ident(_)))
+            && self.peek_at(1) == Some(&Token::LParen)
+            && !self.peek_keyword("size");
+        if is_function {
+            let name = self.next()?.to_string().to_lowercase();
+            self.pos += 1;
+            let mut args = vec![self.operand()?];
+            while self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+                args.push(self.operand()?);
+            }
+            self.expect(Token::RParen)?;
+            return Ok(Node::Leaf(Leaf::Function(name, args)));
+        }
+
+        let left = self.operand()?;
+        if self.eat_keyword("BETWEEN") {
+            let low = self.operand()?;
+            if !self.eat_keyword("AND") {
+                return match self.peek() {
+                    Some(token) => Err(ExprError::UnexpectedToken(token.to_string())),
+                    None => Err(ExprError::UnexpectedEnd),
+                };
+            }
+            let high = self.operand()?;
+            return Ok(Node::Leaf(Leaf::Between(left, low, high)));
+        }
+        if self.peek_keyword("IN") {
+            return Err(ExprError::Unsupported("IN operator".to_owned()));
+        }
+        let comparator = match self.next()? {
+            Token::Eq => Comparator::Eq,
+            Token::Ne => Comparator::Ne,
+            Token::Lt => Comparator::Lt,
+            Token::Le => Comparator::Le,
+            Token::Gt => Comparator::Gt,
+            Token::Ge => Comparator::Ge,
+            token => return Err(ExprError::UnexpectedToken(token.to_string())),
+        };
+        let right = self.operand()?;
+        Ok(Node::Leaf(Leaf::Compare(left, comparator, right)))
+    }
+
+    fn attr_or_value(&mut self) -> Result<AttrOrValue, ExprError> {
+        if matches!(self.peek(), Some(Token::Ident(_))) && self.peek_at(1) == Some(&Token::LParen) {
+            let name = self.next()?;
+            return Err(ExprError::Unsupported(format!("{} function", name)));
+        }
+        match self.next()? {
+            Token::Value(placeholder) => Ok(AttrOrValue::Value(self.resolve_value(&placeholder)?)),
+            token => Ok(AttrOrValue::Attr(self.resolve_name(&token)?)),
+        }
+    }
+
+    fn set_action(&mut self) -> Result<SetAction, ExprError> {
+        let attr = self.path()?;
+        self.expect(Token::Eq)?;
+        let value = self.attr_or_value()?;
+        let operator = match self.peek() {
+            Some(Token::Plus) => Some(ArithmeticOperator::Add),
+            Some(Token::Minus) => Some(ArithmeticOperator::Sub),
+            _ => None,
+        };
+        let operation = match operator {
+            Some(operator) => {
+                self.pos += 1;
+                Some((operator, self.attr_or_value()?))
+            }
+            None => None,
+        };
+        Ok(SetAction {
+            attr,
+            value,
+            operation,
+        })
+    }
+
+    fn is_clause_keyword(&self) -> bool {
+        ["SET", "REMOVE", "ADD", "DELETE"]
+            .iter()
+            .any(|keyword| self.peek_keyword(keyword))
+    }
+
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, ExprError>,
+    ) -> Result<Vec<T>, ExprError> {
+        let mut items = vec![item(self)?];
+        while self.peek() == Some(&Token::Comma) {
+            self.pos += 1;
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    pub(crate) fn update(&mut self) -> Result<UpdateExpr, ExprError> {
+        let mut expr = UpdateExpr::default();
+        let mut seen: Vec<String> = vec![];
+        while self.peek().is_some() {
+            if !self.is_clause_keyword() {
+                return Err(ExprError::UnexpectedToken(self.next()?.to_string()));
+            }
+            let keyword = self.next()?.to_string().to_uppercase();
+            if seen.contains(&keyword) {
+                return Err(ExprError::DuplicateClause(keyword));
+            }
+            match keyword.as_str() {
+                "SET" => expr.set = self.list(Self::set_action)?,
+                "REMOVE" => expr.remove = self.list(Self::path)?,
+                "ADD" => expr.add = self.list(|p| Ok((p.path()?, p.value()?)))?,
+                _ => expr.delete = self.list(|p| Ok((p.path()?, p.value()?)))?,
+            }
+            seen.push(keyword);
+        }
+        if seen.is_empty() {
+            return Err(ExprError::UnexpectedEnd);
+        }
+        Ok(expr)
+    }
+
+    pub(crate) fn projection(&mut self) -> Result<Vec<String>, ExprError> {
+        let attrs = self.list(Self::path)?;
+        self.finish()?;
+        Ok(attrs)
+    }
+}
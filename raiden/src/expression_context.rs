@@ -0,0 +1,215 @@
+use super::{AttributeNames, AttributeValues, DeferredConversionError, Placeholders, RaidenError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionClause {
+    Condition,
+    KeyCondition,
+    Filter,
+    Update,
+    Projection,
+}
+
+// Collects expression attribute names and values of a single request.
+// Every clause shares the same placeholder allocator and registers into this context,
+// so the request gets one consistent `ExpressionAttributeNames`/`ExpressionAttributeValues` pair.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpressionContext {
+    placeholders: Placeholders,
+    clauses: Vec<(ExpressionClause, AttributeNames, AttributeValues)>,
+    error: Option<DeferredConversionError>,
+}
+
+impl ExpressionContext {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn placeholders(&mut self) -> &mut Placeholders {
+        &mut self.placeholders
+    }
+
+    // Registers names and values used by the clause and returns its expression.
+    // Registering the same clause again replaces the previous one, e.g. when `condition()` is called twice.
+    pub fn register(
+        &mut self,
+        clause: ExpressionClause,
+        (expression, names, values): (String, AttributeNames, AttributeValues),
+    ) -> String {
+        self.clauses.retain(|(c, _, _)| c != &clause);
+        self.clauses.push((clause, names, values));
+        expression
+    }
+
+    // Like `register`, but keeps the error of a clause whose values failed to convert.
+    pub fn try_register(
+        &mut self,
+        clause: ExpressionClause,
+        rendered: Result<(String, AttributeNames, AttributeValues), DeferredConversionError>,
+    ) -> Option<String> {
+        match rendered {
+            Ok(rendered) => Some(self.register(clause, rendered)),
+            Err(error) => {
+                self.error.get_or_insert(error);
+                None
+            }
+        }
+    }
+
+    // Where builders keep the first value of the request which failed to convert.
+    pub fn deferred_error(&mut self) -> &mut Option<DeferredConversionError> {
+        &mut self.error
+    }
+
+    // Merges all registered clauses into the given maps.
+    // A placeholder bound to different names or values is reported as `RaidenError::ExpressionConflict`,
+    // and a value which failed to convert as `RaidenError::AttributeConvertError`.
+    pub fn apply(
+        self,
+        names: &mut Option<AttributeNames>,
+        values: &mut Option<AttributeValues>,
+    ) -> Result<(), RaidenError> {
+        if let Some(error) = self.error {
+            return Err(error.into());
+        }
+        let mut merged_names = names.take().unwrap_or_default();
+        let mut merged_values = values.take().unwrap_or_default();
+        for (_, clause_names, clause_values) in self.clauses {
+            merge_checked(&mut merged_names, clause_names)?;
+            merge_checked(&mut merged_values, clause_values)?;
+        }
+        if !merged_names.is_empty() {
+            *names = Some(merged_names);
+        }
+        if !merged_values.is_empty() {
+            *values = Some(merged_values);
+        }
+        Ok(())
+    }
+}
+
+fn merge_checked<T: PartialEq>(
+    dest: &mut std::collections::HashMap<String, T>,
+    src: std::collections::HashMap<String, T>,
+) -> Result<(), RaidenError> {
+    let mut src: Vec<(String, T)> = src.into_iter().collect();
+    src.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (placeholder, value) in src {
+        match dest.get(&placeholder) {
+            Some(existing) if existing != &value => {
+                return Err(RaidenError::ExpressionConflict { placeholder });
+            }
+            _ => {
+                dest.insert(placeholder, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoAttribute;
+
+    fn names(pairs: &[(&str, &str)]) -> AttributeNames {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_clauses_into_existing_maps() {
+        let mut context = ExpressionContext::new();
+        let value = "bokuweb".into_attr();
+        let placeholder = context.placeholders().push(value.clone());
+        let mut values = AttributeValues::new();
+        values.insert(placeholder, value);
+        context.register(
+            ExpressionClause::Filter,
+            (
+                "#name = :value0".to_owned(),
+                names(&[("#name", "name")]),
+                values,
+            ),
+        );
+
+        let mut attr_names = Some(names(&[("#id", "id"), ("#name", "name")]));
+        let mut attr_values = None;
+        context.apply(&mut attr_names, &mut attr_values).unwrap();
+        assert_eq!(attr_names, Some(names(&[("#id", "id"), ("#name", "name")])));
+        assert_eq!(attr_values.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_register_replaces_same_clause() {
+        let mut context = ExpressionContext::new();
+        context.register(
+            ExpressionClause::Condition,
+            (
+                "attribute_exists(#id)".to_owned(),
+                names(&[("#id", "id")]),
+                AttributeValues::new(),
+            ),
+        );
+        context.register(
+            ExpressionClause::Condition,
+            (
+                "attribute_exists(#name)".to_owned(),
+                names(&[("#name", "name")]),
+                AttributeValues::new(),
+            ),
+        );
+
+        let mut attr_names = None;
+        let mut attr_values = None;
+        context.apply(&mut attr_names, &mut attr_values).unwrap();
+        assert_eq!(attr_names, Some(names(&[("#name", "name")])));
+        assert_eq!(attr_values, None);
+    }
+
+    #[test]
+    fn test_report_conversion_error() {
+        let error = DeferredConversionError {
+            attr_name: "tags".to_owned(),
+            source: crate::ConversionError::new(crate::ConversionErrorKind::Serde),
+        };
+        let mut context = ExpressionContext::new();
+        assert_eq!(
+            context.try_register(ExpressionClause::Condition, Err(error.clone())),
+            None
+        );
+        assert_eq!(
+            context.apply(&mut None, &mut None),
+            Err(RaidenError::from(error))
+        );
+    }
+
+    #[test]
+    fn test_detect_conflict() {
+        let mut context = ExpressionContext::new();
+        let mut values = AttributeValues::new();
+        values.insert(":value0".to_owned(), 1.into_attr());
+        context.register(
+            ExpressionClause::Condition,
+            (
+                "#num = :value0".to_owned(),
+                names(&[("#num", "num")]),
+                values,
+            ),
+        );
+
+        let mut attr_names = None;
+        let mut attr_values = Some(AttributeValues::new());
+        attr_values
+            .as_mut()
+            .unwrap()
+            .insert(":value0".to_owned(), 2.into_attr());
+        assert_eq!(
+            context.apply(&mut attr_names, &mut attr_values),
+            Err(RaidenError::ExpressionConflict {
+                placeholder: ":value0".to_owned()
+            })
+        );
+    }
+}
@@ -1,54 +1,100 @@
-use crate::IntoAttribute;
+use crate::{DeferredConversionError, IntoAttribute};
 
 pub type FilterExpressionString = String;
 
 // note: The syntax for a filter expression is identical to that of a key condition expression.
 // Filter expressions can use the same comparators, functions, and logical operators as a key condition expression, with the addition of the not-equals operator (<>).
 // ref: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.html
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FilterExpressionOperator {
-    And(
-        FilterExpressionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
-    Or(
-        FilterExpressionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
+    And(Box<FilterExpr>),
+    Or(Box<FilterExpr>),
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum FilterExpressionTypes {
-    Eq(super::Placeholder, super::AttributeValue),
-    Not(super::Placeholder, super::AttributeValue),
-    Le(super::Placeholder, super::AttributeValue),
-    Ge(super::Placeholder, super::AttributeValue),
-    Lt(super::Placeholder, super::AttributeValue),
-    Gt(super::Placeholder, super::AttributeValue),
-    Between(
-        super::Placeholder,
-        super::AttributeValue,
-        super::Placeholder,
-        super::AttributeValue,
-    ),
-    BeginsWith(super::Placeholder, super::AttributeValue),
+    Eq(super::AttributeValue),
+    Not(super::AttributeValue),
+    Le(super::AttributeValue),
+    Ge(super::AttributeValue),
+    Lt(super::AttributeValue),
+    Gt(super::AttributeValue),
+    Between(super::AttributeValue, super::AttributeValue),
+    BeginsWith(super::AttributeValue),
     AttributeExists(),
     AttributeNotExists(),
-    AttributeType(super::Placeholder, super::AttributeType),
-    Contains(super::Placeholder, super::AttributeValue),
+    AttributeType(super::AttributeType),
+    Contains(super::AttributeValue),
+}
+
+// An already built or parsed tree, typed for one table by e.g. `User::filter_from(expr)`.
+#[derive(Debug, Clone)]
+pub struct FilterExpressionFromExpr<T> {
+    pub expr: FilterExpr,
+    pub _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> FilterExpressionBuilder<T> for FilterExpressionFromExpr<T> {
+    fn into_expr(self) -> FilterExpr {
+        self.expr
+    }
 }
 
 pub trait FilterExpressionBuilder<T> {
+    fn into_expr(self) -> FilterExpr;
+
+    // A value which failed to convert. `build` ignores it, `try_build_with` returns it.
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        None
+    }
+
+    fn try_build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> Result<
+        (
+            FilterExpressionString,
+            super::AttributeNames,
+            super::AttributeValues,
+        ),
+        DeferredConversionError,
+    >
+    where
+        Self: Sized,
+    {
+        match self.conversion_error() {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.build_with(placeholders)),
+        }
+    }
+
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        FilterExpressionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    )
+    where
+        Self: Sized,
+    {
+        self.into_expr().render(placeholders)
+    }
+
     fn build(
         self,
     ) -> (
         FilterExpressionString,
         super::AttributeNames,
         super::AttributeValues,
-    );
+    )
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut super::Placeholders::new())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +109,7 @@ pub struct FilterExpressionFilledOrWaitOperator<T> {
     attr: String,
     is_size: bool,
     cond: FilterExpressionTypes,
+    error: Option<DeferredConversionError>,
     _token: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -72,151 +119,29 @@ pub struct FilterExpressionFilled<T> {
     is_size: bool,
     cond: FilterExpressionTypes,
     operator: FilterExpressionOperator,
+    error: Option<DeferredConversionError>,
     _token: std::marker::PhantomData<fn() -> T>,
 }
 
-impl<T> FilterExpressionFilledOrWaitOperator<T> {
-    pub fn and(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        FilterExpressionFilled {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond: self.cond,
-            operator: FilterExpressionOperator::And(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
-    }
-    pub fn or(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        FilterExpressionFilled {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond: self.cond,
-            operator: FilterExpressionOperator::Or(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
-    }
-}
-
-impl<T> FilterExpressionBuilder<T> for FilterExpressionFilledOrWaitOperator<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let attr_name = self.attr;
-        let mut attr_names: super::AttributeNames = std::collections::HashMap::new();
-        let mut attr_values: super::AttributeValues = std::collections::HashMap::new();
-
-        attr_names.insert(format!("#{}", attr_name), attr_name.clone());
-        let left_cond = if self.is_size {
-            format!("size(#{})", attr_name)
-        } else {
-            format!("#{}", attr_name)
-        };
-        match self.cond {
-            FilterExpressionTypes::Eq(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} = {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Not(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} <> {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Gt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} > {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Ge(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} >= {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Le(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} <= {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Lt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{} < {}", left_cond, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Between(placeholder1, value1, placeholder2, value2) => {
-                attr_values.insert(placeholder1.to_string(), value1);
-                attr_values.insert(placeholder2.to_string(), value2);
-                (
-                    format!(
-                        "{} BETWEEN {} AND {}",
-                        left_cond, placeholder1, placeholder2
-                    ),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::BeginsWith(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("begins_with(#{}, {})", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::AttributeExists() => (
-                format!("attribute_exists(#{})", attr_name),
-                attr_names,
-                attr_values,
-            ),
-            FilterExpressionTypes::AttributeNotExists() => (
-                format!("attribute_not_exists(#{})", attr_name),
-                attr_names,
-                attr_values,
-            ),
-            FilterExpressionTypes::AttributeType(placeholder, attribute_type) => {
-                attr_values.insert(placeholder.to_string(), attribute_type.into_attr());
-                (
-                    format!("attribute_type(#{}, {})", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Contains(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("contains(#{}, {})", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-        }
-    }
+// Untyped filter expression tree. Values are kept as is and placeholders are allocated on rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterExpr {
+    pub attr: String,
+    pub is_size: bool,
+    pub cond: FilterExpressionTypes,
+    pub operator: Option<FilterExpressionOperator>,
 }
 
-impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let (right_str, right_names, right_values) = match self.operator {
-            FilterExpressionOperator::And(s, m, v) => (format!("AND ({})", s), m, v),
-            FilterExpressionOperator::Or(s, m, v) => (format!("OR ({})", s), m, v),
-        };
-
-        let attr_name = self.attr;
+impl FilterExpr {
+    pub fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        FilterExpressionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        let attr_name = &self.attr;
         let mut left_names: super::AttributeNames = std::collections::HashMap::new();
         let mut left_values: super::AttributeValues = std::collections::HashMap::new();
         left_names.insert(format!("#{}", attr_name), attr_name.clone());
@@ -226,42 +151,40 @@ impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
             format!("#{}", attr_name)
         };
 
-        let left_str = match self.cond {
-            FilterExpressionTypes::Eq(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} = {}", left_cond, placeholder)
+        let mut push = |value: super::AttributeValue| {
+            let placeholder = placeholders.push(value.clone());
+            left_values.insert(placeholder.clone(), value);
+            placeholder
+        };
+        let left_str = match &self.cond {
+            FilterExpressionTypes::Eq(value) => {
+                format!("{} = {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Not(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} <> {}", left_cond, placeholder)
+            FilterExpressionTypes::Not(value) => {
+                format!("{} <> {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Gt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} > {}", left_cond, placeholder)
+            FilterExpressionTypes::Gt(value) => {
+                format!("{} > {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Ge(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} >= {}", left_cond, placeholder)
+            FilterExpressionTypes::Ge(value) => {
+                format!("{} >= {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Le(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} <= {}", left_cond, placeholder)
+            FilterExpressionTypes::Le(value) => {
+                format!("{} <= {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Lt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("{} < {}", left_cond, placeholder)
+            FilterExpressionTypes::Lt(value) => {
+                format!("{} < {}", left_cond, push(value.clone()))
             }
-            FilterExpressionTypes::Between(placeholder1, value1, placeholder2, value2) => {
-                left_values.insert(placeholder1.clone(), value1);
-                left_values.insert(placeholder2.clone(), value2);
+            FilterExpressionTypes::Between(value1, value2) => {
+                let placeholder1 = push(value1.clone());
+                let placeholder2 = push(value2.clone());
                 format!(
                     "{} BETWEEN {} AND {}",
                     left_cond, placeholder1, placeholder2
                 )
             }
-            FilterExpressionTypes::BeginsWith(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("begins_with(#{}, {})", attr_name, placeholder)
+            FilterExpressionTypes::BeginsWith(value) => {
+                format!("begins_with(#{}, {})", attr_name, push(value.clone()))
             }
             FilterExpressionTypes::AttributeExists() => {
                 format!("attribute_exists(#{})", attr_name)
@@ -269,14 +192,25 @@ impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
             FilterExpressionTypes::AttributeNotExists() => {
                 format!("attribute_not_exists(#{})", attr_name)
             }
-            FilterExpressionTypes::AttributeType(placeholder, attribute_type) => {
-                left_values.insert(placeholder.clone(), attribute_type.into_attr());
+            FilterExpressionTypes::AttributeType(attribute_type) => {
+                let placeholder = push(attribute_type.clone().into_attr());
                 format!("attribute_type(#{}, {})", attr_name, placeholder)
             }
-            FilterExpressionTypes::Contains(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("contains(#{}, {})", attr_name, placeholder)
+            FilterExpressionTypes::Contains(value) => {
+                format!("contains(#{}, {})", attr_name, push(value.clone()))
+            }
+        };
+
+        let (right_str, right_names, right_values) = match &self.operator {
+            Some(FilterExpressionOperator::And(expr)) => {
+                let (s, m, v) = expr.render(placeholders);
+                (format!("AND ({})", s), m, v)
+            }
+            Some(FilterExpressionOperator::Or(expr)) => {
+                let (s, m, v) = expr.render(placeholders);
+                (format!("OR ({})", s), m, v)
             }
+            None => return (left_str, left_names, left_values),
         };
         (
             format!("{} {}", left_str, right_str),
@@ -286,75 +220,99 @@ impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
     }
 }
 
-impl<T> FilterExpression<T> {
-    pub fn size(mut self) -> Self {
-        self.is_size = true;
-        self
+impl<T> FilterExpressionFilledOrWaitOperator<T> {
+    pub fn and(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
+        self.join(cond, FilterExpressionOperator::And)
+    }
+    pub fn or(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
+        self.join(cond, FilterExpressionOperator::Or)
     }
 
-    pub fn eq(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Eq(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
+    fn join(
+        self,
+        cond: impl FilterExpressionBuilder<T>,
+        operator: fn(Box<FilterExpr>) -> FilterExpressionOperator,
+    ) -> FilterExpressionFilled<T> {
+        let error = self.error.or_else(|| cond.conversion_error().cloned());
+        FilterExpressionFilled {
             attr: self.attr,
             is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: operator(Box::new(cond.into_expr())),
+            error,
+            _token: self._token,
         }
     }
+}
 
-    pub fn not(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Not(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
+impl<T> FilterExpressionBuilder<T> for FilterExpressionFilledOrWaitOperator<T> {
+    fn into_expr(self) -> FilterExpr {
+        FilterExpr {
             attr: self.attr,
             is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: None,
         }
     }
 
-    pub fn gt(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Gt(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        self.error.as_ref()
+    }
+}
+
+impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
+    fn into_expr(self) -> FilterExpr {
+        FilterExpr {
             attr: self.attr,
             is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: Some(self.operator),
         }
     }
-    pub fn ge(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Ge(placeholder, value.into_attr());
+
+    fn conversion_error(&self) -> Option<&DeferredConversionError> {
+        self.error.as_ref()
+    }
+}
+
+impl<T> FilterExpression<T> {
+    pub fn size(mut self) -> Self {
+        self.is_size = true;
+        self
+    }
+
+    fn filled(self, cond: FilterExpressionTypes) -> FilterExpressionFilledOrWaitOperator<T> {
         FilterExpressionFilledOrWaitOperator {
             attr: self.attr,
             is_size: self.is_size,
             cond,
+            error: None,
             _token: std::marker::PhantomData,
         }
     }
 
+    pub fn eq(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.filled(FilterExpressionTypes::Eq(value.into_attr()))
+    }
+
+    pub fn not(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.filled(FilterExpressionTypes::Not(value.into_attr()))
+    }
+
+    pub fn gt(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.filled(FilterExpressionTypes::Gt(value.into_attr()))
+    }
+
+    pub fn ge(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.filled(FilterExpressionTypes::Ge(value.into_attr()))
+    }
+
     pub fn le(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Le(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Le(value.into_attr()))
     }
 
     pub fn lt(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Lt(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Lt(value.into_attr()))
     }
 
     pub fn between(
@@ -362,20 +320,10 @@ impl<T> FilterExpression<T> {
         value1: impl super::IntoAttribute,
         value2: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder1 = format!(":value{}", super::generate_value_id());
-        let placeholder2 = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Between(
-            placeholder1,
+        self.filled(FilterExpressionTypes::Between(
             value1.into_attr(),
-            placeholder2,
             value2.into_attr(),
-        );
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        ))
     }
 
     // We can use `begins_with` only with a range key after specifying an EQ condition for the primary key.
@@ -383,61 +331,145 @@ impl<T> FilterExpression<T> {
         self,
         value: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::BeginsWith(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::BeginsWith(value.into_attr()))
     }
 
     pub fn attribute_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
-        let cond = FilterExpressionTypes::AttributeExists();
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeExists())
     }
 
     pub fn attribute_not_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
-        let cond = FilterExpressionTypes::AttributeNotExists();
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeNotExists())
     }
 
     pub fn attribute_type(
         self,
         attribute_type: super::AttributeType,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::AttributeType(placeholder, attribute_type);
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeType(attribute_type))
     }
 
     pub fn contains(
         self,
         value: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Contains(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
+        self.filled(FilterExpressionTypes::Contains(value.into_attr()))
+    }
+}
+
+// Per-field filter expression whose values are typed by the field, e.g. `User::filter().age().gt(30u32)`.
+#[derive(Debug, Clone)]
+pub struct TypedFilterExpression<T, V> {
+    inner: FilterExpression<T>,
+    encode: super::ValueEncoder<V>,
+}
+
+impl<T, V: super::IntoAttribute> TypedFilterExpression<T, V> {
+    pub fn new(attr: String) -> Self {
+        Self::with_encoder(attr, super::encode_into_attr::<V>)
+    }
+}
+
+impl<T, V> TypedFilterExpression<T, V> {
+    // For a field which isn't stored with `IntoAttribute`.
+    pub fn with_encoder(attr: String, encode: super::ValueEncoder<V>) -> Self {
+        Self {
+            inner: FilterExpression {
+                attr,
+                is_size: false,
+                _token: std::marker::PhantomData,
+            },
+            encode,
+        }
+    }
+
+    // `size()` is always compared with a number regardless of the field type.
+    pub fn size(self) -> TypedFilterExpression<T, usize> {
+        TypedFilterExpression {
+            inner: self.inner.size(),
+            encode: super::encode_into_attr::<usize>,
         }
     }
+
+    fn value(
+        &self,
+        value: V,
+        error: &mut Option<DeferredConversionError>,
+    ) -> super::AttributeValue {
+        DeferredConversionError::collect(error, &self.inner.attr, (self.encode)(value))
+    }
+
+    fn cmp(
+        self,
+        cond: fn(super::AttributeValue) -> FilterExpressionTypes,
+        value: V,
+    ) -> FilterExpressionFilledOrWaitOperator<T> {
+        let mut error = None;
+        let value = self.value(value, &mut error);
+        let mut filled = self.inner.filled(cond(value));
+        filled.error = error;
+        filled
+    }
+
+    pub fn eq(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Eq, value)
+    }
+
+    pub fn not(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Not, value)
+    }
+
+    pub fn gt(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Gt, value)
+    }
+
+    pub fn ge(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Ge, value)
+    }
+
+    pub fn le(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Le, value)
+    }
+
+    pub fn lt(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::Lt, value)
+    }
+
+    pub fn between(self, value1: V, value2: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        let mut error = None;
+        let value1 = self.value(value1, &mut error);
+        let value2 = self.value(value2, &mut error);
+        let mut filled = self
+            .inner
+            .filled(FilterExpressionTypes::Between(value1, value2));
+        filled.error = error;
+        filled
+    }
+
+    pub fn begins_with(self, value: V) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.cmp(FilterExpressionTypes::BeginsWith, value)
+    }
+
+    pub fn attribute_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.inner.attribute_exists()
+    }
+
+    pub fn attribute_not_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.inner.attribute_not_exists()
+    }
+
+    pub fn attribute_type(
+        self,
+        attribute_type: super::AttributeType,
+    ) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.inner.attribute_type(attribute_type)
+    }
+
+    // `contains` takes a substring for strings or an element for sets and lists, so the value is not tied to the field type.
+    pub fn contains(
+        self,
+        value: impl super::IntoAttribute,
+    ) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.inner.contains(value)
+    }
 }
@@ -0,0 +1,224 @@
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::{
+    errors::{parse_number, take_attr},
+    AttributeType, ConversionErrorKind,
+};
+use crate::{AttributeValue, ConversionError};
+
+// Converts `T` with a representation selected by `#[raiden(format = "...")]`.
+pub trait AttributeFormat<T> {
+    // Fails when `value` has no representation in the format.
+    fn into_attr(value: T) -> Result<AttributeValue, ConversionError>;
+    fn from_attr(value: Option<AttributeValue>) -> Result<T, ConversionError>;
+}
+
+// UTC timestamp string with a fixed number of fractional digits, so that stored values sort chronologically.
+#[derive(Debug, Clone, Copy)]
+pub struct Rfc3339;
+
+// Milliseconds since the Unix epoch, stored as a number.
+#[derive(Debug, Clone, Copy)]
+pub struct EpochMillis;
+
+macro_rules! option_format {
+    ($format:ty) => {
+        impl<T> AttributeFormat<Option<T>> for $format
+        where
+            $format: AttributeFormat<T>,
+        {
+            fn into_attr(value: Option<T>) -> Result<AttributeValue, ConversionError> {
+                match value {
+                    Some(value) => <$format>::into_attr(value),
+                    None => Ok(AttributeValue {
+                        null: Some(true),
+                        ..AttributeValue::default()
+                    }),
+                }
+            }
+
+            fn from_attr(value: Option<AttributeValue>) -> Result<Option<T>, ConversionError> {
+                match value {
+                    None => Ok(None),
+                    Some(value) if value.null == Some(true) => Ok(None),
+                    value => Ok(Some(<$format>::from_attr(value)?)),
+                }
+            }
+        }
+    };
+}
+
+option_format!(Rfc3339);
+option_format!(EpochMillis);
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn string_attr(value: String) -> AttributeValue {
+    AttributeValue {
+        s: Some(value),
+        ..AttributeValue::default()
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn number_attr(value: i64) -> AttributeValue {
+    AttributeValue {
+        n: Some(value.to_string()),
+        ..AttributeValue::default()
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn string_from_attr(value: Option<AttributeValue>) -> Result<String, ConversionError> {
+    take_attr(value, AttributeType::S, |value| value.s.take())
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn number_from_attr(value: Option<AttributeValue>) -> Result<i64, ConversionError> {
+    let n = take_attr(value, AttributeType::N, |value| value.n.take())?;
+    parse_number(&n, ConversionErrorKind::ParseInt)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn date_time_error(expected: AttributeType) -> ConversionError {
+    ConversionError::new(ConversionErrorKind::ParseDateTime).with_expected(expected)
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impl {
+    use super::*;
+    use crate::{FromAttribute, IntoAttribute};
+    use chrono::{DateTime, NaiveDate, SecondsFormat, TimeZone, Utc};
+
+    impl AttributeFormat<DateTime<Utc>> for Rfc3339 {
+        fn into_attr(value: DateTime<Utc>) -> Result<AttributeValue, ConversionError> {
+            Ok(IntoAttribute::into_attr(value))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<DateTime<Utc>, ConversionError> {
+            DateTime::parse_from_rfc3339(&string_from_attr(value)?)
+                .map(|datetime| datetime.with_timezone(&Utc))
+                .map_err(|e| date_time_error(AttributeType::S).with_cause(e))
+        }
+    }
+
+    impl AttributeFormat<DateTime<Utc>> for EpochMillis {
+        fn into_attr(value: DateTime<Utc>) -> Result<AttributeValue, ConversionError> {
+            Ok(number_attr(value.timestamp_millis()))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<DateTime<Utc>, ConversionError> {
+            Utc.timestamp_millis_opt(number_from_attr(value)?)
+                .single()
+                .ok_or_else(|| date_time_error(AttributeType::N))
+        }
+    }
+
+    impl AttributeFormat<NaiveDate> for Rfc3339 {
+        fn into_attr(value: NaiveDate) -> Result<AttributeValue, ConversionError> {
+            Ok(IntoAttribute::into_attr(value))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<NaiveDate, ConversionError> {
+            NaiveDate::parse_from_str(&string_from_attr(value)?, "%Y-%m-%d")
+                .map_err(|e| date_time_error(AttributeType::S).with_cause(e))
+        }
+    }
+
+    // Stored as midnight UTC of the date.
+    impl AttributeFormat<NaiveDate> for EpochMillis {
+        fn into_attr(value: NaiveDate) -> Result<AttributeValue, ConversionError> {
+            let midnight = value
+                .and_hms_opt(0, 0, 0)
+                .ok_or_else(|| date_time_error(AttributeType::N))?;
+            EpochMillis::into_attr(Utc.from_utc_datetime(&midnight))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<NaiveDate, ConversionError> {
+            let datetime: DateTime<Utc> = EpochMillis::from_attr(value)?;
+            Ok(datetime.naive_utc().date())
+        }
+    }
+
+    impl IntoAttribute for DateTime<Utc> {
+        fn into_attr(self) -> AttributeValue {
+            string_attr(self.to_rfc3339_opts(SecondsFormat::Nanos, true))
+        }
+    }
+
+    impl FromAttribute for DateTime<Utc> {
+        fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+            Rfc3339::from_attr(value)
+        }
+    }
+
+    impl IntoAttribute for NaiveDate {
+        fn into_attr(self) -> AttributeValue {
+            string_attr(self.format("%Y-%m-%d").to_string())
+        }
+    }
+
+    impl FromAttribute for NaiveDate {
+        fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+            Rfc3339::from_attr(value)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impl {
+    use super::*;
+    use crate::{FromAttribute, IntoAttribute};
+    use std::convert::TryFrom;
+    use time::format_description::well_known::Rfc3339 as WellKnownRfc3339;
+    use time::{OffsetDateTime, UtcOffset};
+
+    impl AttributeFormat<OffsetDateTime> for Rfc3339 {
+        fn into_attr(value: OffsetDateTime) -> Result<AttributeValue, ConversionError> {
+            Ok(IntoAttribute::into_attr(value))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<OffsetDateTime, ConversionError> {
+            OffsetDateTime::parse(&string_from_attr(value)?, &WellKnownRfc3339)
+                .map(|datetime| datetime.to_offset(UtcOffset::UTC))
+                .map_err(|e| date_time_error(AttributeType::S).with_cause(e))
+        }
+    }
+
+    impl AttributeFormat<OffsetDateTime> for EpochMillis {
+        // Floors like chrono's `timestamp_millis`, so times before 1970 are stored alike.
+        fn into_attr(value: OffsetDateTime) -> Result<AttributeValue, ConversionError> {
+            let millis = value.unix_timestamp_nanos().div_euclid(1_000_000);
+            i64::try_from(millis)
+                .map(number_attr)
+                .map_err(|e| date_time_error(AttributeType::N).with_cause(e))
+        }
+
+        fn from_attr(value: Option<AttributeValue>) -> Result<OffsetDateTime, ConversionError> {
+            let millis = i128::from(number_from_attr(value)?);
+            OffsetDateTime::from_unix_timestamp_nanos(millis * 1_000_000)
+                .map_err(|e| date_time_error(AttributeType::N).with_cause(e))
+        }
+    }
+
+    // Written from its components, since `OffsetDateTime::format` is fallible.
+    impl IntoAttribute for OffsetDateTime {
+        fn into_attr(self) -> AttributeValue {
+            let value = self.to_offset(UtcOffset::UTC);
+            string_attr(format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}Z",
+                value.year(),
+                u8::from(value.month()),
+                value.day(),
+                value.hour(),
+                value.minute(),
+                value.second(),
+                value.nanosecond()
+            ))
+        }
+    }
+
+    impl FromAttribute for OffsetDateTime {
+        fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+            Rfc3339::from_attr(value)
+        }
+    }
+}
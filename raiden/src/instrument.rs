@@ -0,0 +1,409 @@
+use crate::{ErrorContext, RaidenError};
+
+// A span wrapping one operation, including its pages and retries.
+// Without the `tracing` feature it only measures the latency of the operation.
+// Fields follow the OpenTelemetry semantic conventions for DynamoDB, see
+// https://opentelemetry.io/docs/specs/semconv/database/dynamodb/
+pub struct OperationSpan {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    started_at: wasm_timer::Instant,
+}
+
+impl OperationSpan {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn new(context: &ErrorContext) -> Self {
+        let span = Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::info_span!(
+                "dynamodb",
+                otel.name = %format!("DynamoDB.{}", context.operation),
+                otel.kind = "client",
+                otel.status_code = tracing::field::Empty,
+                otel.status_description = tracing::field::Empty,
+                db.system = "dynamodb",
+                db.operation = %context.operation,
+                rpc.system = "aws-api",
+                rpc.service = "DynamoDB",
+                rpc.method = %context.operation,
+                aws.dynamodb.table_names = tracing::field::Empty,
+                aws.dynamodb.index_name = tracing::field::Empty,
+                aws.dynamodb.consumed_capacity = tracing::field::Empty,
+                aws.dynamodb.count = tracing::field::Empty,
+                aws.dynamodb.scanned_count = tracing::field::Empty,
+                raiden.item_count = tracing::field::Empty,
+                raiden.attempts = tracing::field::Empty,
+                raiden.latency_ms = tracing::field::Empty,
+            ),
+            started_at: wasm_timer::Instant::now(),
+        };
+        span.with_table_names(context.table_name.as_deref())
+    }
+
+    // For operations spanning tables, e.g. transactions.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn with_table_names<'a>(self, table_names: impl IntoIterator<Item = &'a str>) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(table_names) = table_names_attribute(table_names) {
+                self.span
+                    .record("aws.dynamodb.table_names", table_names.as_str());
+            }
+        }
+        self
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn with_index_name(self, index_name: Option<&str>) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(index_name) = index_name {
+                self.span.record("aws.dynamodb.index_name", index_name);
+            }
+        }
+        self
+    }
+
+    // Number of items written, for operations whose output doesn't tell it.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn with_item_count(self, count: usize) -> Self {
+        #[cfg(feature = "tracing")]
+        self.span.record("raiden.item_count", count as u64);
+        self
+    }
+
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.started_at.elapsed()
+    }
+
+    // Runs `future` inside the span.
+    pub async fn instrument<F: std::future::Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            tracing::Instrument::instrument(future, self.span.clone()).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future.await
+        }
+    }
+
+    // Records the outcome of the operation once all its requests are done.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub fn finish<T: SpanOutput>(&self, attempts: usize, result: &Result<T, RaidenError>) {
+        #[cfg(feature = "tracing")]
+        {
+            let span = &self.span;
+            span.record("raiden.attempts", attempts as u64);
+            span.record("raiden.latency_ms", self.elapsed().as_secs_f64() * 1000.0);
+            match result {
+                Ok(output) => {
+                    if let Some(count) = output.item_count() {
+                        span.record("raiden.item_count", count as u64);
+                    }
+                    if let Some(count) = output.count() {
+                        span.record("aws.dynamodb.count", count);
+                    }
+                    if let Some(count) = output.scanned_count() {
+                        span.record("aws.dynamodb.scanned_count", count);
+                    }
+                    if let Some(consumed_capacity) =
+                        consumed_capacity_attribute(&output.consumed_capacity())
+                    {
+                        span.record("aws.dynamodb.consumed_capacity", consumed_capacity.as_str());
+                    }
+                }
+                Err(e) => {
+                    span.record("otel.status_code", "ERROR");
+                    span.record("otel.status_description", tracing::field::display(e));
+                }
+            }
+        }
+    }
+}
+
+// A string array as JSON, e.g. `["user","project"]`, since tracing has no array values.
+#[cfg(feature = "tracing")]
+fn table_names_attribute<'a>(table_names: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut names: Vec<&str> = vec![];
+    for name in table_names {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if names.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&names).ok()
+}
+
+// Each capacity as JSON, e.g. `["{\"TableName\":\"user\",\"CapacityUnits\":0.5}"]`.
+#[cfg(feature = "tracing")]
+fn consumed_capacity_attribute(consumed_capacity: &[&crate::ConsumedCapacity]) -> Option<String> {
+    if consumed_capacity.is_empty() {
+        return None;
+    }
+    let capacities = consumed_capacity
+        .iter()
+        .filter_map(|capacity| serde_json::to_string(capacity).ok())
+        .collect::<Vec<_>>();
+    serde_json::to_string(&capacities).ok()
+}
+
+// What an operation's output tells its span.
+pub trait SpanOutput {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        vec![]
+    }
+
+    // Number of items read or written.
+    fn item_count(&self) -> Option<usize> {
+        None
+    }
+
+    // `Count` and `ScannedCount` of queries and scans.
+    fn count(&self) -> Option<i64> {
+        None
+    }
+
+    fn scanned_count(&self) -> Option<i64> {
+        None
+    }
+}
+
+impl SpanOutput for () {}
+
+impl<T> SpanOutput for crate::get::GetOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl<T> SpanOutput for crate::put::PutOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl<T> SpanOutput for crate::update::UpdateOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl<T> SpanOutput for crate::query::QueryOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn count(&self) -> Option<i64> {
+        self.count
+    }
+
+    fn scanned_count(&self) -> Option<i64> {
+        self.scanned_count
+    }
+}
+
+impl<T> SpanOutput for crate::scan::ScanOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn count(&self) -> Option<i64> {
+        self.count
+    }
+
+    fn scanned_count(&self) -> Option<i64> {
+        self.scanned_count
+    }
+}
+
+impl<T> SpanOutput for crate::batch_get::BatchGetOutput<T> {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().flatten().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+impl SpanOutput for crate::batch_delete::BatchDeleteOutput {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().flatten().collect()
+    }
+}
+
+impl SpanOutput for crate::delete::DeleteOutput {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().collect()
+    }
+
+    fn item_count(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
+impl SpanOutput for crate::TransactWriteOutput {
+    fn consumed_capacity(&self) -> Vec<&crate::ConsumedCapacity> {
+        self.consumed_capacity.iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outputs_tell_consumed_capacity_and_counts() {
+        let capacity = crate::ConsumedCapacity {
+            table_name: Some("user".to_owned()),
+            capacity_units: Some(0.5),
+            ..crate::ConsumedCapacity::default()
+        };
+        let output = crate::query::QueryOutput::<()> {
+            consumed_capacity: Some(capacity.clone()),
+            items: vec![(), ()],
+            count: Some(2),
+            next_token: None,
+            scanned_count: Some(3),
+        };
+        assert_eq!(output.consumed_capacity(), vec![&capacity]);
+        assert_eq!(output.item_count(), Some(2));
+        assert_eq!(output.scanned_count(), Some(3));
+
+        let output = crate::batch_delete::BatchDeleteOutput {
+            consumed_capacity: Some(vec![capacity.clone(), capacity.clone()]),
+            unprocessed_items: vec![],
+        };
+        assert_eq!(output.consumed_capacity().len(), 2);
+        assert_eq!(output.item_count(), None);
+    }
+
+    #[tokio::test]
+    async fn test_span_runs_future() {
+        let context = ErrorContext::new(crate::Operation::GetItem).with_table_name("user");
+        let span = OperationSpan::new(&context)
+            .with_index_name(Some("testGSI"))
+            .with_item_count(1);
+        let result: Result<(), RaidenError> = span.instrument(async { Ok(()) }).await;
+        span.finish(1, &result);
+        assert!(result.is_ok());
+    }
+
+    // Keeps the last value recorded to each field of the spans it sees.
+    #[cfg(feature = "tracing")]
+    struct FieldRecorder(
+        std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, String>>>,
+    );
+
+    #[cfg(feature = "tracing")]
+    impl tracing::field::Visit for FieldRecorder {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            let mut fields = self.0.lock().unwrap();
+            fields.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            let mut fields = self.0.lock().unwrap();
+            fields.insert(field.name().to_owned(), format!("{:?}", value));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for FieldRecorder {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            span.record(&mut FieldRecorder(self.0.clone()));
+            tracing::span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            values.record(&mut FieldRecorder(self.0.clone()));
+        }
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_span_records_fields() {
+        let fields = std::sync::Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+        tracing::subscriber::with_default(FieldRecorder(fields.clone()), || {
+            let context = ErrorContext::new(crate::Operation::Query).with_table_name("user");
+            let span = OperationSpan::new(&context).with_index_name(Some("testGSI"));
+            let result: Result<_, RaidenError> = Ok(crate::query::QueryOutput::<()> {
+                consumed_capacity: None,
+                items: vec![(), ()],
+                count: Some(2),
+                next_token: None,
+                scanned_count: Some(3),
+            });
+            span.finish(2, &result);
+        });
+        let fields = fields.lock().unwrap();
+        assert_eq!(fields["otel.name"], "DynamoDB.Query");
+        assert_eq!(fields["db.system"], "dynamodb");
+        assert_eq!(fields["aws.dynamodb.table_names"], r#"["user"]"#);
+        assert_eq!(fields["aws.dynamodb.index_name"], "testGSI");
+        assert_eq!(fields["aws.dynamodb.count"], "2");
+        assert_eq!(fields["aws.dynamodb.scanned_count"], "3");
+        assert_eq!(fields["raiden.item_count"], "2");
+        assert_eq!(fields["raiden.attempts"], "2");
+        assert!(!fields.contains_key("otel.status_code"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_table_names_attribute() {
+        assert_eq!(table_names_attribute(None), None);
+        assert_eq!(
+            table_names_attribute(vec!["user", "project", "user"]),
+            Some(r#"["user","project"]"#.to_owned())
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_consumed_capacity_attribute() {
+        let capacity = crate::ConsumedCapacity {
+            table_name: Some("user".to_owned()),
+            capacity_units: Some(0.5),
+            ..crate::ConsumedCapacity::default()
+        };
+        assert_eq!(consumed_capacity_attribute(&[]), None);
+        let attribute = consumed_capacity_attribute(&[&capacity]).unwrap();
+        let values: Vec<String> = serde_json::from_str(&attribute).unwrap();
+        assert_eq!(values, vec![serde_json::to_string(&capacity).unwrap()]);
+    }
+}
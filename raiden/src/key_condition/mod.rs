@@ -1,39 +1,89 @@
 pub type KeyConditionString = String;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum KeyConditionOperator {
-    And(
-        KeyConditionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
+    And(Box<KeyConditionExpr>),
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum KeyConditionTypes {
-    Eq(super::Placeholder, super::AttributeValue),
-    Le(super::Placeholder, super::AttributeValue),
-    Ge(super::Placeholder, super::AttributeValue),
-    Lt(super::Placeholder, super::AttributeValue),
-    Gt(super::Placeholder, super::AttributeValue),
-    Between(
-        super::Placeholder,
-        super::AttributeValue,
-        super::Placeholder,
-        super::AttributeValue,
-    ),
-    BeginsWith(super::Placeholder, super::AttributeValue),
+    Eq(super::AttributeValue),
+    Le(super::AttributeValue),
+    Ge(super::AttributeValue),
+    Lt(super::AttributeValue),
+    Gt(super::AttributeValue),
+    Between(super::AttributeValue, super::AttributeValue),
+    BeginsWith(super::AttributeValue),
+}
+
+// An already built or parsed tree, typed for one table by e.g. `User::key_condition_from(expr)`.
+#[derive(Debug, Clone)]
+pub struct KeyConditionFromExpr<T> {
+    pub expr: KeyConditionExpr,
+    pub _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> KeyConditionBuilder<T> for KeyConditionFromExpr<T> {
+    fn into_expr(self) -> KeyConditionExpr {
+        self.expr
+    }
 }
 
 pub trait KeyConditionBuilder<T> {
+    fn into_expr(self) -> KeyConditionExpr;
+
+    // A value which failed to convert. `build` ignores it, `try_build_with` returns it.
+    fn conversion_error(&self) -> Option<&super::DeferredConversionError> {
+        None
+    }
+
+    fn try_build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> Result<
+        (
+            KeyConditionString,
+            super::AttributeNames,
+            super::AttributeValues,
+        ),
+        super::DeferredConversionError,
+    >
+    where
+        Self: Sized,
+    {
+        match self.conversion_error() {
+            Some(error) => Err(error.clone()),
+            None => Ok(self.build_with(placeholders)),
+        }
+    }
+
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        KeyConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    )
+    where
+        Self: Sized,
+    {
+        self.into_expr().render(placeholders)
+    }
+
     fn build(
         self,
     ) -> (
         KeyConditionString,
         super::AttributeNames,
         super::AttributeValues,
-    );
+    )
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut super::Placeholders::new())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -54,149 +104,62 @@ pub struct KeyConditionFilled<T> {
     attr: String,
     cond: KeyConditionTypes,
     operator: KeyConditionOperator,
+    error: Option<super::DeferredConversionError>,
     _token: std::marker::PhantomData<fn() -> T>,
 }
 
-impl<T> KeyConditionFilledOrWaitOperator<T> {
-    pub fn and(self, cond: impl KeyConditionBuilder<T>) -> KeyConditionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        KeyConditionFilled {
-            attr: self.attr,
-            cond: self.cond,
-            operator: KeyConditionOperator::And(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
-    }
-}
-
-impl<T> KeyConditionBuilder<T> for KeyConditionFilledOrWaitOperator<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let attr_name = self.attr;
-        let mut attr_names: super::AttributeNames = std::collections::HashMap::new();
-        let mut attr_values: super::AttributeValues = std::collections::HashMap::new();
-
-        attr_names.insert(format!("#{}", attr_name), attr_name.clone());
-        match self.cond {
-            super::key_condition::KeyConditionTypes::Eq(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{} = {}", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Gt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{} > {}", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Ge(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{} >= {}", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Le(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{} <= {}", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Lt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{} < {}", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Between(
-                placeholder1,
-                value1,
-                placeholder2,
-                value2,
-            ) => {
-                attr_values.insert(placeholder1.to_string(), value1);
-                attr_values.insert(placeholder2.to_string(), value2);
-                (
-                    format!(
-                        "#{} BETWEEN {} AND {}",
-                        attr_name, placeholder1, placeholder2
-                    ),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("begins_with(#{}, {})", attr_name, placeholder),
-                    attr_names,
-                    attr_values,
-                )
-            }
-        }
-    }
+// Untyped key condition tree. Values are kept as is and placeholders are allocated on rendering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyConditionExpr {
+    pub attr: String,
+    pub cond: KeyConditionTypes,
+    pub operator: Option<KeyConditionOperator>,
 }
 
-impl<T> KeyConditionBuilder<T> for KeyConditionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let (right_str, right_names, right_values) = match self.operator {
-            super::key_condition::KeyConditionOperator::And(s, m, v) => {
-                (format!("AND ({})", s), m, v)
-            }
-        };
-
-        let attr_name = self.attr;
+impl KeyConditionExpr {
+    pub fn render(
+        &self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        KeyConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        let attr_name = &self.attr;
         let mut left_names: super::AttributeNames = std::collections::HashMap::new();
         let mut left_values: super::AttributeValues = std::collections::HashMap::new();
         left_names.insert(format!("#{}", attr_name), attr_name.clone());
 
-        let left_str = match self.cond {
-            super::key_condition::KeyConditionTypes::Eq(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{} = {}", attr_name, placeholder)
-            }
-            super::key_condition::KeyConditionTypes::Gt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{} > {}", attr_name, placeholder)
-            }
-            super::key_condition::KeyConditionTypes::Ge(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{} >= {}", attr_name, placeholder)
-            }
-            super::key_condition::KeyConditionTypes::Le(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{} <= {}", attr_name, placeholder)
-            }
-            super::key_condition::KeyConditionTypes::Lt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{} < {}", attr_name, placeholder)
-            }
-            super::key_condition::KeyConditionTypes::Between(
-                placeholder1,
-                value1,
-                placeholder2,
-                value2,
-            ) => {
-                left_values.insert(placeholder1.clone(), value1);
-                left_values.insert(placeholder2.clone(), value2);
+        let mut push = |value: &super::AttributeValue| {
+            let placeholder = placeholders.push(value.clone());
+            left_values.insert(placeholder.clone(), value.clone());
+            placeholder
+        };
+        let left_str = match &self.cond {
+            KeyConditionTypes::Eq(value) => format!("#{} = {}", attr_name, push(value)),
+            KeyConditionTypes::Gt(value) => format!("#{} > {}", attr_name, push(value)),
+            KeyConditionTypes::Ge(value) => format!("#{} >= {}", attr_name, push(value)),
+            KeyConditionTypes::Le(value) => format!("#{} <= {}", attr_name, push(value)),
+            KeyConditionTypes::Lt(value) => format!("#{} < {}", attr_name, push(value)),
+            KeyConditionTypes::Between(value1, value2) => {
+                let placeholder1 = push(value1);
+                let placeholder2 = push(value2);
                 format!(
                     "#{} BETWEEN {} AND {}",
                     attr_name, placeholder1, placeholder2
                 )
             }
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("begins_with(#{}, {})", attr_name, placeholder)
+            KeyConditionTypes::BeginsWith(value) => {
+                format!("begins_with(#{}, {})", attr_name, push(value))
+            }
+        };
+
+        let (right_str, right_names, right_values) = match &self.operator {
+            Some(KeyConditionOperator::And(expr)) => {
+                let (s, m, v) = expr.render(placeholders);
+                (format!("AND ({})", s), m, v)
             }
+            None => return (left_str, left_names, left_values),
         };
         (
             format!("{} {}", left_str, right_str),
@@ -206,39 +169,45 @@ impl<T> KeyConditionBuilder<T> for KeyConditionFilled<T> {
     }
 }
 
-impl<T> KeyCondition<T> {
-    pub fn eq(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Eq(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
+impl<T> KeyConditionFilledOrWaitOperator<T> {
+    pub fn and(self, cond: impl KeyConditionBuilder<T>) -> KeyConditionFilled<T> {
+        let error = cond.conversion_error().cloned();
+        KeyConditionFilled {
             attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: KeyConditionOperator::And(Box::new(cond.into_expr())),
+            error,
+            _token: self._token,
         }
     }
+}
 
-    pub fn gt(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Gt(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
+impl<T> KeyConditionBuilder<T> for KeyConditionFilledOrWaitOperator<T> {
+    fn into_expr(self) -> KeyConditionExpr {
+        KeyConditionExpr {
             attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: None,
         }
     }
-    pub fn ge(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Ge(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
+}
+
+impl<T> KeyConditionBuilder<T> for KeyConditionFilled<T> {
+    fn into_expr(self) -> KeyConditionExpr {
+        KeyConditionExpr {
             attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
+            cond: self.cond,
+            operator: Some(self.operator),
         }
     }
 
-    pub fn le(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Le(placeholder, value.into_attr());
+    fn conversion_error(&self) -> Option<&super::DeferredConversionError> {
+        self.error.as_ref()
+    }
+}
+
+impl<T> KeyCondition<T> {
+    fn filled(self, cond: KeyConditionTypes) -> KeyConditionFilledOrWaitOperator<T> {
         KeyConditionFilledOrWaitOperator {
             attr: self.attr,
             cond,
@@ -246,14 +215,24 @@ impl<T> KeyCondition<T> {
         }
     }
 
+    pub fn eq(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
+        self.filled(KeyConditionTypes::Eq(value.into_attr()))
+    }
+
+    pub fn gt(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
+        self.filled(KeyConditionTypes::Gt(value.into_attr()))
+    }
+
+    pub fn ge(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
+        self.filled(KeyConditionTypes::Ge(value.into_attr()))
+    }
+
+    pub fn le(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
+        self.filled(KeyConditionTypes::Le(value.into_attr()))
+    }
+
     pub fn lt(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Lt(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Lt(value.into_attr()))
     }
 
     pub fn between(
@@ -261,19 +240,10 @@ impl<T> KeyCondition<T> {
         value1: impl super::IntoAttribute,
         value2: impl super::IntoAttribute,
     ) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder1 = format!(":value{}", super::generate_value_id());
-        let placeholder2 = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Between(
-            placeholder1,
+        self.filled(KeyConditionTypes::Between(
             value1.into_attr(),
-            placeholder2,
             value2.into_attr(),
-        );
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        ))
     }
 
     // We can use `begins_with` only with a range key after specifying an EQ condition for the primary key.
@@ -281,13 +251,184 @@ impl<T> KeyCondition<T> {
         self,
         value: impl super::IntoAttribute,
     ) -> KeyConditionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond =
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
+        self.filled(KeyConditionTypes::BeginsWith(value.into_attr()))
+    }
+}
+
+// Key condition on the table's partition key, e.g. `User::keys().id().eq("id0".to_owned())`.
+// DynamoDB only accepts `=` on a partition key, and only a sort key condition may follow it.
+#[derive(Debug, Clone)]
+pub struct PartitionKeyCondition<T, V> {
+    attr: String,
+    encode: super::ValueEncoder<V>,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionKeyConditionFilled<T> {
+    attr: String,
+    value: super::AttributeValue,
+    error: Option<super::DeferredConversionError>,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T, V: super::IntoAttribute> PartitionKeyCondition<T, V> {
+    pub fn new(attr: String) -> Self {
+        Self::with_encoder(attr, super::encode_into_attr::<V>)
+    }
+}
+
+impl<T, V> PartitionKeyCondition<T, V> {
+    // For a key which isn't stored with `IntoAttribute`.
+    pub fn with_encoder(attr: String, encode: super::ValueEncoder<V>) -> Self {
+        Self {
+            attr,
+            encode,
+            _token: std::marker::PhantomData,
+        }
+    }
+
+    pub fn eq(self, value: V) -> PartitionKeyConditionFilled<T> {
+        let mut error = None;
+        let value =
+            super::DeferredConversionError::collect(&mut error, &self.attr, (self.encode)(value));
+        PartitionKeyConditionFilled {
             attr: self.attr,
-            cond,
+            value,
+            error,
+            _token: self._token,
+        }
+    }
+}
+
+impl<T> PartitionKeyConditionFilled<T> {
+    pub fn and(self, cond: SortKeyConditionFilled<T>) -> KeyConditionFilled<T> {
+        let error = self.error.or_else(|| cond.error.clone());
+        KeyConditionFilled {
+            attr: self.attr,
+            cond: KeyConditionTypes::Eq(self.value),
+            operator: KeyConditionOperator::And(Box::new(cond.into_expr())),
+            error,
+            _token: self._token,
+        }
+    }
+}
+
+impl<T> KeyConditionBuilder<T> for PartitionKeyConditionFilled<T> {
+    fn into_expr(self) -> KeyConditionExpr {
+        KeyConditionExpr {
+            attr: self.attr,
+            cond: KeyConditionTypes::Eq(self.value),
+            operator: None,
+        }
+    }
+
+    fn conversion_error(&self) -> Option<&super::DeferredConversionError> {
+        self.error.as_ref()
+    }
+}
+
+// Key condition on the table's sort key. It can only be joined to a partition key condition.
+#[derive(Debug, Clone)]
+pub struct SortKeyCondition<T, V> {
+    attr: String,
+    encode: super::ValueEncoder<V>,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SortKeyConditionFilled<T> {
+    attr: String,
+    cond: KeyConditionTypes,
+    error: Option<super::DeferredConversionError>,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> SortKeyConditionFilled<T> {
+    fn into_expr(self) -> KeyConditionExpr {
+        KeyConditionExpr {
+            attr: self.attr,
+            cond: self.cond,
+            operator: None,
+        }
+    }
+}
+
+impl<T, V: super::IntoAttribute> SortKeyCondition<T, V> {
+    pub fn new(attr: String) -> Self {
+        Self::with_encoder(attr, super::encode_into_attr::<V>)
+    }
+}
+
+impl<T, V> SortKeyCondition<T, V> {
+    // For a key which isn't stored with `IntoAttribute`.
+    pub fn with_encoder(attr: String, encode: super::ValueEncoder<V>) -> Self {
+        Self {
+            attr,
+            encode,
             _token: std::marker::PhantomData,
         }
     }
+
+    fn filled(
+        self,
+        cond: KeyConditionTypes,
+        error: Option<super::DeferredConversionError>,
+    ) -> SortKeyConditionFilled<T> {
+        SortKeyConditionFilled {
+            attr: self.attr,
+            cond,
+            error,
+            _token: self._token,
+        }
+    }
+
+    fn value(
+        &self,
+        value: V,
+        error: &mut Option<super::DeferredConversionError>,
+    ) -> super::AttributeValue {
+        super::DeferredConversionError::collect(error, &self.attr, (self.encode)(value))
+    }
+
+    fn cmp(
+        self,
+        cond: fn(super::AttributeValue) -> KeyConditionTypes,
+        value: V,
+    ) -> SortKeyConditionFilled<T> {
+        let mut error = None;
+        let value = self.value(value, &mut error);
+        self.filled(cond(value), error)
+    }
+
+    pub fn eq(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::Eq, value)
+    }
+
+    pub fn gt(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::Gt, value)
+    }
+
+    pub fn ge(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::Ge, value)
+    }
+
+    pub fn le(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::Le, value)
+    }
+
+    pub fn lt(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::Lt, value)
+    }
+
+    pub fn between(self, value1: V, value2: V) -> SortKeyConditionFilled<T> {
+        let mut error = None;
+        let value1 = self.value(value1, &mut error);
+        let value2 = self.value(value2, &mut error);
+        self.filled(KeyConditionTypes::Between(value1, value2), error)
+    }
+
+    pub fn begins_with(self, value: V) -> SortKeyConditionFilled<T> {
+        self.cmp(KeyConditionTypes::BeginsWith, value)
+    }
 }
@@ -1,25 +1,46 @@
 #[macro_use]
 extern crate serde_derive;
 
+pub mod binary;
+pub mod capacity;
 pub mod condition;
+pub mod empty_value;
 pub mod errors;
+pub mod expr;
+pub mod expression_context;
 pub mod filter_expression;
+pub mod format;
 pub mod id_generator;
+pub mod instrument;
 pub mod key_condition;
+pub mod metrics;
+pub mod middleware;
 pub mod next_token;
+pub mod number;
 pub mod ops;
+pub mod request_id;
 pub mod retry;
+pub mod serde_attr;
 pub mod types;
 pub mod update_expression;
 pub mod value_id;
 
+pub use binary::*;
+pub use capacity::*;
 pub use condition::*;
+pub use empty_value::*;
 pub use errors::*;
+pub use expression_context::*;
 pub use filter_expression::*;
+pub use instrument::*;
 pub use key_condition::*;
+pub use metrics::*;
+pub use middleware::*;
 pub use next_token::*;
+pub use number::*;
 pub use ops::*;
 pub use retry::*;
+pub use serde_attr::{from_item, to_item};
 
 pub use id_generator::*;
 pub use raiden_derive::*;
@@ -42,6 +63,9 @@ pub type Placeholder = String;
 
 pub use safe_builder::Builder;
 
+// For implementing `Layer`.
+pub use async_trait::async_trait;
+
 #[derive(Debug, Clone, PartialEq)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum AttributeType {
@@ -80,6 +104,11 @@ pub struct Attributes(AttributeValues);
 
 pub trait IntoAttrName: Sized + Copy {
     fn into_attr_name(self) -> String;
+
+    // How an empty value set to this attribute by an update is written.
+    fn empty_value_policy(self) -> EmptyValuePolicy {
+        EmptyValuePolicy::default()
+    }
 }
 
 pub trait ToAttrNames: Sized {
@@ -102,22 +131,17 @@ pub trait IntoAttribute: Sized {
     fn into_attr(self) -> AttributeValue;
 }
 
-#[derive(Debug)]
-pub enum ConversionError {
-    ValueIsNone,
-    ParseInt,
-}
+// Converts the value of a typed expression the way its field is stored,
+// e.g. with `#[raiden(format = "...")]` or `#[raiden(serde)]`.
+pub type ValueEncoder<V> = fn(V) -> Result<AttributeValue, ConversionError>;
 
-impl std::fmt::Display for ConversionError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ConversionError::ValueIsNone => write!(f, "Value is none"),
-            ConversionError::ParseInt => write!(f, "Parsing error of integer"),
-        }
-    }
+pub(crate) fn encode_into_attr<V: IntoAttribute>(
+    value: V,
+) -> Result<AttributeValue, ConversionError> {
+    Ok(value.into_attr())
 }
 
-impl std::error::Error for ConversionError {}
+use errors::{parse_number, take_attr};
 
 pub trait FromAttribute: Sized {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError>;
@@ -140,27 +164,19 @@ impl IntoAttribute for String {
 
 impl FromAttribute for String {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        if value.is_none() {
-            return Err(ConversionError::ValueIsNone);
-        }
-        let value = value.unwrap();
-        if let Some(true) = value.null {
-            // See. https://github.com/raiden-rs/raiden/issues/58
-            return Ok("".to_owned());
-        }
-        value.s.ok_or(ConversionError::ValueIsNone)
+        take_attr(value, AttributeType::S, |value| {
+            if let Some(true) = value.null {
+                // See. https://github.com/raiden-rs/raiden/issues/58
+                return Some("".to_owned());
+            }
+            value.s.take()
+        })
     }
 }
 
 impl IntoAttribute for &'_ str {
     fn into_attr(self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden-dynamo/issues/58
-            return AttributeValue {
-                null: Some(true),
-                ..Default::default()
-            };
-        }
+        // An empty string is written according to `EmptyValuePolicy`.
         AttributeValue {
             s: Some(self.to_owned()),
             ..AttributeValue::default()
@@ -174,13 +190,6 @@ impl<'a> IntoAttribute for std::borrow::Cow<'a, str> {
             std::borrow::Cow::Owned(o) => o,
             std::borrow::Cow::Borrowed(b) => b.to_owned(),
         };
-        if s.is_empty() {
-            // See. https://github.com/raiden-rs/raiden-dynamo/issues/58
-            return AttributeValue {
-                null: Some(true),
-                ..Default::default()
-            };
-        }
         AttributeValue {
             s: Some(s),
             ..AttributeValue::default()
@@ -190,23 +199,15 @@ impl<'a> IntoAttribute for std::borrow::Cow<'a, str> {
 
 impl<'a> FromAttribute for std::borrow::Cow<'a, str> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        if value.is_none() {
-            return Err(ConversionError::ValueIsNone);
-        }
-        let value = value.unwrap();
-        if let Some(true) = value.null {
-            // See. https://github.com/raiden-rs/raiden/issues/58
-            return Ok(std::borrow::Cow::Owned("".to_owned()));
-        }
-        value
-            .s
-            .map(std::borrow::Cow::Owned)
-            .ok_or(ConversionError::ValueIsNone)
+        String::from_attr(value).map(std::borrow::Cow::Owned)
     }
 }
 
 macro_rules! default_attr_for_num {
     ($to: ty) => {
+        default_attr_for_num!($to, ParseInt);
+    };
+    ($to: ty, $err: ident) => {
         impl IntoAttribute for $to {
             fn into_attr(self) -> AttributeValue {
                 AttributeValue {
@@ -217,14 +218,8 @@ macro_rules! default_attr_for_num {
         }
         impl FromAttribute for $to {
             fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                if value.is_none() {
-                    return Err(ConversionError::ValueIsNone);
-                }
-                value
-                    .unwrap()
-                    .n
-                    .map(|v| v.parse().unwrap())
-                    .ok_or(ConversionError::ValueIsNone)
+                let n = take_attr(value, AttributeType::N, |value| value.n.take())?;
+                parse_number(&n, ConversionErrorKind::$err)
             }
         }
     };
@@ -242,8 +237,8 @@ default_attr_for_num!(i32);
 default_attr_for_num!(i16);
 default_attr_for_num!(i8);
 
-default_attr_for_num!(f32);
-default_attr_for_num!(f64);
+default_attr_for_num!(f32, ParseNumber);
+default_attr_for_num!(f64, ParseNumber);
 
 impl<T: IntoAttribute> IntoAttribute for Option<T> {
     fn into_attr(self) -> AttributeValue {
@@ -281,10 +276,7 @@ impl IntoAttribute for bool {
 
 impl FromAttribute for bool {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        if value.is_none() {
-            return Err(ConversionError::ValueIsNone);
-        }
-        value.unwrap().bool.ok_or(ConversionError::ValueIsNone)
+        take_attr(value, AttributeType::BOOL, |value| value.bool.take())
     }
 }
 
@@ -302,13 +294,7 @@ impl FromStringSetItem for String {
 
 impl<A: IntoAttribute> IntoAttribute for Vec<A> {
     fn into_attr(mut self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            return AttributeValue {
-                null: Some(true),
-                ..Default::default()
-            };
-        }
+        // An empty list is written according to `EmptyValuePolicy`.
         AttributeValue {
             l: Some(self.drain(..).map(|s| s.into_attr()).collect()),
             ..AttributeValue::default()
@@ -326,17 +312,147 @@ impl<A: FromAttribute> FromAttribute for Vec<A> {
             // See. https://github.com/raiden-rs/raiden/issues/57
             return Ok(vec![]);
         }
-        value
-            .l
-            .ok_or(ConversionError::ValueIsNone)?
+        take_attr(Some(value), AttributeType::L, |value| value.l.take())?
             .into_iter()
-            .map(|item| A::from_attr(Some(item)))
+            .enumerate()
+            .map(|(i, item)| A::from_attr(Some(item)).map_err(|e| e.at_index(i)))
             .collect()
     }
 }
 
+macro_rules! default_map_convertor {
+    ($map: ident) => {
+        impl<A: IntoAttribute> IntoAttribute for std::collections::$map<String, A> {
+            fn into_attr(self) -> AttributeValue {
+                AttributeValue {
+                    m: Some(self.into_iter().map(|(k, v)| (k, v.into_attr())).collect()),
+                    ..AttributeValue::default()
+                }
+            }
+        }
+
+        impl<A: FromAttribute> FromAttribute for std::collections::$map<String, A> {
+            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+                if value.is_none() {
+                    return Ok(std::collections::$map::new());
+                }
+                let value = value.unwrap();
+                if let Some(true) = value.null {
+                    return Ok(std::collections::$map::new());
+                }
+                take_attr(Some(value), AttributeType::M, |value| value.m.take())?
+                    .into_iter()
+                    .map(|(k, v)| match A::from_attr(Some(v)) {
+                        Ok(v) => Ok((k, v)),
+                        Err(e) => Err(e.at_key(k)),
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+default_map_convertor!(HashMap);
+default_map_convertor!(BTreeMap);
+
+impl IntoAttribute for serde_json::Value {
+    fn into_attr(self) -> AttributeValue {
+        match self {
+            serde_json::Value::Null => AttributeValue {
+                null: Some(true),
+                ..AttributeValue::default()
+            },
+            serde_json::Value::Bool(b) => b.into_attr(),
+            serde_json::Value::Number(n) => AttributeValue {
+                n: Some(n.to_string()),
+                ..AttributeValue::default()
+            },
+            serde_json::Value::String(s) => s.into_attr(),
+            serde_json::Value::Array(a) => AttributeValue {
+                l: Some(a.into_iter().map(|v| v.into_attr()).collect()),
+                ..AttributeValue::default()
+            },
+            serde_json::Value::Object(o) => AttributeValue {
+                m: Some(o.into_iter().map(|(k, v)| (k, v.into_attr())).collect()),
+                ..AttributeValue::default()
+            },
+        }
+    }
+}
+
+fn json_number(n: &str) -> Result<serde_json::Value, ConversionError> {
+    parse_number(n, ConversionErrorKind::ParseNumber).map(serde_json::Value::Number)
+}
+
+impl FromAttribute for serde_json::Value {
+    fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        let value = match value {
+            Some(value) => value,
+            None => return Ok(serde_json::Value::Null),
+        };
+        if let Some(true) = value.null {
+            return Ok(serde_json::Value::Null);
+        }
+        if let Some(s) = value.s {
+            return Ok(serde_json::Value::String(s));
+        }
+        if let Some(n) = value.n {
+            return json_number(&n);
+        }
+        if let Some(b) = value.bool {
+            return Ok(serde_json::Value::Bool(b));
+        }
+        if let Some(l) = value.l {
+            return l
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| serde_json::Value::from_attr(Some(v)).map_err(|e| e.at_index(i)))
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Array);
+        }
+        if let Some(m) = value.m {
+            return m
+                .into_iter()
+                .map(|(k, v)| match serde_json::Value::from_attr(Some(v)) {
+                    Ok(v) => Ok((k, v)),
+                    Err(e) => Err(e.at_key(k)),
+                })
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Object);
+        }
+        // Sets have no JSON counterpart, so they are read as arrays. Binary is base64 encoded.
+        if let Some(ss) = value.ss {
+            return Ok(serde_json::Value::Array(
+                ss.into_iter().map(serde_json::Value::String).collect(),
+            ));
+        }
+        if let Some(ns) = value.ns {
+            return ns
+                .iter()
+                .enumerate()
+                .map(|(i, n)| json_number(n).map_err(|e| e.at_index(i)))
+                .collect::<Result<_, _>>()
+                .map(serde_json::Value::Array);
+        }
+        if let Some(b) = value.b {
+            return Ok(serde_json::Value::String(base64::encode(b)));
+        }
+        if let Some(bs) = value.bs {
+            return Ok(serde_json::Value::Array(
+                bs.into_iter()
+                    .map(|b| serde_json::Value::String(base64::encode(b)))
+                    .collect(),
+            ));
+        }
+        Err(ConversionError::new(ConversionErrorKind::ValueIsNone))
+    }
+}
+
 macro_rules! default_number_set_convertor {
     ($to: ty) => {
+        default_number_set_convertor!($to, ParseInt);
+    };
+    ($to: ty, $err: ident) => {
         impl IntoAttribute for std::collections::HashSet<$to> {
             fn into_attr(self) -> AttributeValue {
                 if self.is_empty() {
@@ -361,12 +477,13 @@ macro_rules! default_number_set_convertor {
                     // See. https://github.com/raiden-rs/raiden/issues/57
                     return Ok(std::collections::HashSet::new());
                 }
-                let mut nums = value.ns.ok_or(ConversionError::ValueIsNone)?;
-                let mut results: Vec<Result<$to, ConversionError>> = nums
-                    .drain(..)
-                    .map(|ns| ns.parse().map_err(|_| ConversionError::ParseInt))
-                    .collect();
-                results.drain(..).collect()
+                take_attr(Some(value), AttributeType::NS, |value| value.ns.take())?
+                    .iter()
+                    .enumerate()
+                    .map(|(i, n)| {
+                        parse_number(n, ConversionErrorKind::$err).map_err(|e| e.at_index(i))
+                    })
+                    .collect()
             }
         }
     };
@@ -384,6 +501,8 @@ default_number_set_convertor!(i32);
 default_number_set_convertor!(i16);
 default_number_set_convertor!(i8);
 
+default_number_set_convertor!(Number, ParseNumber);
+
 impl<A: std::hash::Hash + IntoStringSetItem> IntoAttribute for std::collections::HashSet<A> {
     fn into_attr(self) -> AttributeValue {
         if self.is_empty() {
@@ -410,8 +529,11 @@ impl<A: std::hash::Hash + std::cmp::Eq + FromStringSetItem> FromAttribute
             // See. https://github.com/raiden-rs/raiden/issues/57
             return Ok(std::collections::HashSet::new());
         }
-        let mut ss = value.ss.ok_or(ConversionError::ValueIsNone)?;
-        ss.drain(..).map(A::from_ss_item).collect()
+        take_attr(Some(value), AttributeType::SS, |value| value.ss.take())?
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| A::from_ss_item(item).map_err(|e| e.at_index(i)))
+            .collect()
     }
 }
 
@@ -430,3 +552,17 @@ pub fn merge_map<T>(
 pub fn is_attr_value_empty(a: &AttributeValue) -> bool {
     a == &AttributeValue::default()
 }
+
+// Used by `#[raiden(default_on_error)]` fields, so items written with an older shape can still be read.
+pub fn use_default_on_conversion_error<T>(
+    attr_name: &str,
+    error: ConversionError,
+    default: impl FnOnce() -> T,
+) -> T {
+    log::warn!(
+        "Failed to convert `{}`, using default value instead: {}",
+        attr_name,
+        error
+    );
+    default()
+}
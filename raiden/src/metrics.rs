@@ -0,0 +1,242 @@
+use crate::{
+    ErrorContext, Operation, RaidenError, RaidenTransactionCancellationReason, SpanOutput,
+};
+
+// How an operation ended, to tell expected failures from throttling and bugs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Ok,
+    // A condition expression failed, including in a canceled transaction.
+    ConditionalFailure,
+    Throttle,
+    Other,
+}
+
+impl Outcome {
+    pub fn from_result<T>(result: &Result<T, RaidenError>) -> Self {
+        match result {
+            Ok(_) => Outcome::Ok,
+            Err(e) => Outcome::from_error(e),
+        }
+    }
+
+    pub fn from_error(error: &RaidenError) -> Self {
+        if error.is_throttling() {
+            return Outcome::Throttle;
+        }
+        match error {
+            RaidenError::ConditionalCheckFailed(_) => Outcome::ConditionalFailure,
+            RaidenError::TransactionCanceled { reasons, .. } => {
+                let reasons = reasons.0.iter().flatten();
+                if reasons.clone().any(|reason| {
+                    matches!(
+                        reason,
+                        RaidenTransactionCancellationReason::ProvisionedThroughputExceeded
+                            | RaidenTransactionCancellationReason::ThrottlingError
+                    )
+                }) {
+                    Outcome::Throttle
+                } else if reasons.clone().any(|reason| {
+                    *reason == RaidenTransactionCancellationReason::ConditionalCheckFailed
+                }) {
+                    Outcome::ConditionalFailure
+                } else {
+                    Outcome::Other
+                }
+            }
+            _ => Outcome::Other,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::ConditionalFailure => "conditional_failure",
+            Outcome::Throttle => "throttle",
+            Outcome::Other => "other",
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// What a finished operation reports to `RaidenMetrics`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationMetrics<'a> {
+    pub operation: Operation,
+    // `None` when the operation spans tables.
+    pub table_name: Option<&'a str>,
+    pub outcome: Outcome,
+    // Including pages, retries and backoff.
+    pub latency: std::time::Duration,
+    // Summed over pages and chunks, each of which is retried apart.
+    pub retries: usize,
+    // Items returned, 0 on failure.
+    pub item_count: usize,
+}
+
+impl<'a> OperationMetrics<'a> {
+    pub fn new<T: SpanOutput>(
+        context: &'a ErrorContext,
+        result: &Result<T, RaidenError>,
+        retries: usize,
+        latency: std::time::Duration,
+    ) -> Self {
+        Self {
+            operation: context.operation,
+            table_name: context.table_name.as_deref(),
+            outcome: Outcome::from_result(result),
+            latency,
+            retries,
+            item_count: result
+                .as_ref()
+                .ok()
+                .and_then(|output| output.item_count())
+                .unwrap_or(0),
+        }
+    }
+}
+
+// Called once per operation of a client registered with `with_metrics`.
+pub trait RaidenMetrics: Send + Sync {
+    fn record(&self, _metrics: &OperationMetrics<'_>) {}
+}
+
+impl<M: RaidenMetrics + ?Sized> RaidenMetrics for std::sync::Arc<M> {
+    fn record(&self, metrics: &OperationMetrics<'_>) {
+        (**self).record(metrics)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl RaidenMetrics for NoopMetrics {}
+
+// Reports to the recorder installed for the `metrics` crate:
+// - `raiden_operations_total` counter, labeled with `operation`, `table` and `outcome`.
+// - `raiden_operation_duration_seconds` histogram, labeled with `operation`, `table` and `outcome`.
+// - `raiden_retries_total` and `raiden_items_total` counters, labeled with `operation` and `table`.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsRecorder;
+
+#[cfg(feature = "metrics")]
+impl RaidenMetrics for MetricsRecorder {
+    fn record(&self, metrics: &OperationMetrics<'_>) {
+        let operation = metrics.operation.to_string();
+        let table = metrics.table_name.unwrap_or_default().to_owned();
+        let outcome = metrics.outcome.as_str();
+        ::metrics::counter!(
+            "raiden_operations_total",
+            "operation" => operation.clone(),
+            "table" => table.clone(),
+            "outcome" => outcome
+        )
+        .increment(1);
+        ::metrics::histogram!(
+            "raiden_operation_duration_seconds",
+            "operation" => operation.clone(),
+            "table" => table.clone(),
+            "outcome" => outcome
+        )
+        .record(metrics.latency.as_secs_f64());
+        ::metrics::counter!(
+            "raiden_retries_total",
+            "operation" => operation.clone(),
+            "table" => table.clone()
+        )
+        .increment(metrics.retries as u64);
+        ::metrics::counter!(
+            "raiden_items_total",
+            "operation" => operation,
+            "table" => table
+        )
+        .increment(metrics.item_count as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RaidenTransactionCancellationReasons;
+
+    #[test]
+    fn test_outcome_from_error() {
+        assert_eq!(
+            Outcome::from_error(&RaidenError::ConditionalCheckFailed("failed".to_owned())),
+            Outcome::ConditionalFailure
+        );
+        assert_eq!(
+            Outcome::from_error(&RaidenError::ProvisionedThroughputExceeded(
+                "exceeded".to_owned()
+            )),
+            Outcome::Throttle
+        );
+        assert_eq!(
+            Outcome::from_error(&RaidenError::Validation("invalid".to_owned())),
+            Outcome::Other
+        );
+
+        let canceled = |reasons| RaidenError::TransactionCanceled {
+            reasons: RaidenTransactionCancellationReasons(reasons),
+            failed_items: vec![],
+        };
+        assert_eq!(
+            Outcome::from_error(&canceled(vec![
+                None,
+                Some(RaidenTransactionCancellationReason::ConditionalCheckFailed)
+            ])),
+            Outcome::ConditionalFailure
+        );
+        assert_eq!(
+            Outcome::from_error(&canceled(vec![
+                Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
+                Some(RaidenTransactionCancellationReason::ThrottlingError)
+            ])),
+            Outcome::Throttle
+        );
+        assert_eq!(
+            Outcome::from_error(&canceled(vec![Some(
+                RaidenTransactionCancellationReason::TransactionConflict
+            )])),
+            Outcome::Other
+        );
+    }
+
+    #[test]
+    fn test_operation_metrics() {
+        let context = ErrorContext::new(Operation::Scan).with_table_name("user");
+        let result: Result<_, RaidenError> = Ok(crate::scan::ScanOutput::<()> {
+            consumed_capacity: None,
+            items: vec![(), ()],
+            count: Some(2),
+            last_evaluated_key: None,
+            scanned_count: Some(2),
+        });
+        let metrics =
+            OperationMetrics::new(&context, &result, 2, std::time::Duration::from_millis(5));
+        assert_eq!(
+            metrics,
+            OperationMetrics {
+                operation: Operation::Scan,
+                table_name: Some("user"),
+                outcome: Outcome::Ok,
+                latency: std::time::Duration::from_millis(5),
+                retries: 2,
+                item_count: 2,
+            }
+        );
+
+        let result: Result<(), RaidenError> =
+            Err(RaidenError::ThrottlingException("throttled".to_owned()));
+        let metrics = OperationMetrics::new(&context, &result, 0, std::time::Duration::default());
+        assert_eq!(metrics.outcome, Outcome::Throttle);
+        assert_eq!(metrics.retries, 0);
+        assert_eq!(metrics.item_count, 0);
+    }
+}
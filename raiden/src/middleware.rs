@@ -0,0 +1,343 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::{DynamoDb as _, Operation, RaidenError};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// The input of a request about to be sent to DynamoDB.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Request {
+    GetItem(crate::GetItemInput),
+    BatchGetItem(crate::BatchGetItemInput),
+    Query(crate::QueryInput),
+    Scan(crate::ScanInput),
+    PutItem(crate::PutItemInput),
+    UpdateItem(crate::UpdateItemInput),
+    DeleteItem(crate::DeleteItemInput),
+    BatchWriteItem(crate::BatchWriteItemInput),
+    TransactWriteItems(crate::TransactWriteItemsInput),
+}
+
+impl Request {
+    pub fn operation(&self) -> Operation {
+        match self {
+            Request::GetItem(_) => Operation::GetItem,
+            Request::BatchGetItem(_) => Operation::BatchGetItem,
+            Request::Query(_) => Operation::Query,
+            Request::Scan(_) => Operation::Scan,
+            Request::PutItem(_) => Operation::PutItem,
+            Request::UpdateItem(_) => Operation::UpdateItem,
+            Request::DeleteItem(_) => Operation::DeleteItem,
+            Request::BatchWriteItem(_) => Operation::BatchWriteItem,
+            Request::TransactWriteItems(_) => Operation::TransactWriteItems,
+        }
+    }
+
+    // Every table the request reads or writes, e.g. for tenancy checks.
+    pub fn table_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = match self {
+            Request::GetItem(input) => vec![&input.table_name],
+            Request::BatchGetItem(input) => {
+                input.request_items.keys().map(|k| k.as_str()).collect()
+            }
+            Request::Query(input) => vec![&input.table_name],
+            Request::Scan(input) => vec![&input.table_name],
+            Request::PutItem(input) => vec![&input.table_name],
+            Request::UpdateItem(input) => vec![&input.table_name],
+            Request::DeleteItem(input) => vec![&input.table_name],
+            Request::BatchWriteItem(input) => {
+                input.request_items.keys().map(|k| k.as_str()).collect()
+            }
+            Request::TransactWriteItems(input) => input
+                .transact_items
+                .iter()
+                .filter_map(|item| {
+                    if let Some(put) = &item.put {
+                        Some(put.table_name.as_str())
+                    } else if let Some(update) = &item.update {
+                        Some(update.table_name.as_str())
+                    } else if let Some(delete) = &item.delete {
+                        Some(delete.table_name.as_str())
+                    } else {
+                        item.condition_check
+                            .as_ref()
+                            .map(|condition_check| condition_check.table_name.as_str())
+                    }
+                })
+                .collect(),
+        };
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+// The output DynamoDB answered to a `Request` of the same variant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Response {
+    GetItem(crate::GetItemOutput),
+    BatchGetItem(crate::BatchGetItemOutput),
+    Query(crate::QueryOutput),
+    Scan(crate::ScanOutput),
+    PutItem(crate::PutItemOutput),
+    UpdateItem(crate::UpdateItemOutput),
+    DeleteItem(crate::DeleteItemOutput),
+    BatchWriteItem(crate::BatchWriteItemOutput),
+    TransactWriteItems(crate::TransactWriteItemsOutput),
+}
+
+impl Response {
+    pub fn operation(&self) -> Operation {
+        match self {
+            Response::GetItem(_) => Operation::GetItem,
+            Response::BatchGetItem(_) => Operation::BatchGetItem,
+            Response::Query(_) => Operation::Query,
+            Response::Scan(_) => Operation::Scan,
+            Response::PutItem(_) => Operation::PutItem,
+            Response::UpdateItem(_) => Operation::UpdateItem,
+            Response::DeleteItem(_) => Operation::DeleteItem,
+            Response::BatchWriteItem(_) => Operation::BatchWriteItem,
+            Response::TransactWriteItems(_) => Operation::TransactWriteItems,
+        }
+    }
+}
+
+// A middleware wrapping every request sent by a client, retries included.
+// It can change the request, inspect the result of `next.run(request)` or
+// short-circuit by returning without calling `next`.
+#[async_trait::async_trait]
+pub trait Layer: Send + Sync {
+    async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError>;
+}
+
+#[async_trait::async_trait]
+impl<L: Layer + ?Sized> Layer for Arc<L> {
+    async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+        (**self).call(request, next).await
+    }
+}
+
+// The rest of the pipeline, ending with sending the request to DynamoDB.
+pub struct Next<'a> {
+    layers: &'a [Arc<dyn Layer>],
+    dispatch: Box<dyn FnOnce(Request) -> BoxFuture<'a, Result<Response, RaidenError>> + Send + 'a>,
+}
+
+impl<'a> Next<'a> {
+    pub async fn run(self, request: Request) -> Result<Response, RaidenError> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => {
+                let next = Next {
+                    layers,
+                    dispatch: self.dispatch,
+                };
+                layer.call(request, next).await
+            }
+            None => (self.dispatch)(request).await,
+        }
+    }
+}
+
+// Layers of a client, called in the order they were added.
+#[derive(Clone, Default)]
+pub struct Pipeline {
+    layers: Vec<Arc<dyn Layer>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn push(&mut self, layer: impl Layer + 'static) {
+        self.layers.push(Arc::new(layer));
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    // Passes `request` through the layers, then to `dispatch`.
+    pub async fn send<'a, F, Fut>(
+        &'a self,
+        request: Request,
+        dispatch: F,
+    ) -> Result<Response, RaidenError>
+    where
+        F: FnOnce(Request) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Response, RaidenError>> + Send + 'a,
+    {
+        let next = Next {
+            layers: &self.layers,
+            dispatch: Box::new(move |request| Box::pin(dispatch(request))),
+        };
+        next.run(request).await
+    }
+}
+
+// A layer changed the operation of a request or answered another one.
+pub(crate) fn unexpected_operation(expected: Operation, actual: Operation) -> RaidenError {
+    RaidenError::MiddlewareError(format!("expected {} but got {}", expected, actual))
+}
+
+macro_rules! dispatch {
+    ($($method:ident($variant:ident, $input:ty) -> $output:ty;)*) => {
+        impl Pipeline {
+            $(
+                pub async fn $method(
+                    &self,
+                    client: &crate::DynamoDbClient,
+                    input: $input,
+                ) -> Result<$output, RaidenError> {
+                    let response = self
+                        .send(Request::$variant(input), |request| async move {
+                            match request {
+                                Request::$variant(input) => {
+                                    Ok(Response::$variant(client.$method(input).await?))
+                                }
+                                request => Err(unexpected_operation(
+                                    Operation::$variant,
+                                    request.operation(),
+                                )),
+                            }
+                        })
+                        .await?;
+                    match response {
+                        Response::$variant(output) => Ok(output),
+                        response => Err(unexpected_operation(
+                            Operation::$variant,
+                            response.operation(),
+                        )),
+                    }
+                }
+            )*
+        }
+    };
+}
+
+dispatch! {
+    get_item(GetItem, crate::GetItemInput) -> crate::GetItemOutput;
+    batch_get_item(BatchGetItem, crate::BatchGetItemInput) -> crate::BatchGetItemOutput;
+    query(Query, crate::QueryInput) -> crate::QueryOutput;
+    scan(Scan, crate::ScanInput) -> crate::ScanOutput;
+    put_item(PutItem, crate::PutItemInput) -> crate::PutItemOutput;
+    update_item(UpdateItem, crate::UpdateItemInput) -> crate::UpdateItemOutput;
+    delete_item(DeleteItem, crate::DeleteItemInput) -> crate::DeleteItemOutput;
+    batch_write_item(BatchWriteItem, crate::BatchWriteItemInput) -> crate::BatchWriteItemOutput;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct Record(&'static str, Arc<Mutex<Vec<String>>>);
+
+    #[async_trait::async_trait]
+    impl Layer for Record {
+        async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+            self.1.lock().unwrap().push(format!("{} before", self.0));
+            let result = next.run(request).await;
+            self.1.lock().unwrap().push(format!("{} after", self.0));
+            result
+        }
+    }
+
+    struct Rename;
+
+    #[async_trait::async_trait]
+    impl Layer for Rename {
+        async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+            match request {
+                Request::GetItem(mut input) => {
+                    input.table_name = format!("tenant_{}", input.table_name);
+                    next.run(Request::GetItem(input)).await
+                }
+                request => next.run(request).await,
+            }
+        }
+    }
+
+    struct Deny;
+
+    #[async_trait::async_trait]
+    impl Layer for Deny {
+        async fn call(&self, request: Request, _next: Next<'_>) -> Result<Response, RaidenError> {
+            Err(RaidenError::AccessDenied(request.table_names().join(",")))
+        }
+    }
+
+    fn get_item(table_name: &str) -> Request {
+        Request::GetItem(crate::GetItemInput {
+            table_name: table_name.to_owned(),
+            ..crate::GetItemInput::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_layers_wrap_dispatch_in_order() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut pipeline = Pipeline::new();
+        pipeline.push(Record("outer", calls.clone()));
+        pipeline.push(Rename);
+        pipeline.push(Record("inner", calls.clone()));
+
+        let dispatched = calls.clone();
+        let response = pipeline
+            .send(get_item("user"), |request| async move {
+                dispatched
+                    .lock()
+                    .unwrap()
+                    .push(format!("dispatch {}", request.table_names().join(",")));
+                Ok(Response::GetItem(crate::GetItemOutput::default()))
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.operation(), Operation::GetItem);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "outer before",
+                "inner before",
+                "dispatch tenant_user",
+                "inner after",
+                "outer after"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layer_short_circuits() {
+        let mut pipeline = Pipeline::new();
+        pipeline.push(Deny);
+        let result = pipeline
+            .send(get_item("user"), |_| async {
+                panic!("the request must not be sent");
+            })
+            .await;
+        assert!(matches!(result, Err(RaidenError::AccessDenied(table)) if table == "user"));
+    }
+
+    #[test]
+    fn test_request_table_names() {
+        let put = |table_name: &str| crate::TransactWriteItem {
+            put: Some(crate::Put {
+                table_name: table_name.to_owned(),
+                ..crate::Put::default()
+            }),
+            ..crate::TransactWriteItem::default()
+        };
+        let request = Request::TransactWriteItems(crate::TransactWriteItemsInput {
+            transact_items: vec![put("user"), put("project"), put("user")],
+            ..crate::TransactWriteItemsInput::default()
+        });
+        assert_eq!(request.table_names(), vec!["project", "user"]);
+        assert_eq!(request.operation(), Operation::TransactWriteItems);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::errors::{parse_number, take_attr};
+use crate::{AttributeType, AttributeValue, ConversionError, ConversionErrorKind};
+use crate::{FromAttribute, IntoAttribute};
+
+// DynamoDB keeps up to 38 significant digits.
+const MAX_PRECISION: usize = 38;
+
+// Number attribute kept in its exact textual form, so that no precision is lost on the way through `f64`.
+// Compares by value, e.g. `1` equals `1.0`, as DynamoDB rejects such duplicates in a number set.
+#[derive(Debug, Clone)]
+pub struct Number(String);
+
+impl Number {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // Sign, significant digits and exponent, e.g. `-1.50` is `(true, "15", -1)`. Zero has no digits.
+    fn normalized(&self) -> (bool, String, i64) {
+        let (negative, s) = match self.0.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, self.0.as_str()),
+        };
+        let (mantissa, exponent) = match s.find(&['e', 'E'][..]) {
+            // Bounded by `is_valid_number`.
+            Some(index) => (&s[..index], s[index + 1..].parse::<i64>().unwrap_or(0)),
+            None => (s, 0),
+        };
+        let (integer, fraction) = match mantissa.find('.') {
+            Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+            None => (mantissa, ""),
+        };
+        let digits: String = integer.chars().chain(fraction.chars()).collect();
+        let significant = digits.trim_start_matches('0');
+        let trimmed = significant.trim_end_matches('0');
+        if trimmed.is_empty() {
+            return (false, String::new(), 0);
+        }
+        let exponent =
+            exponent - fraction.len() as i64 + (significant.len() - trimmed.len()) as i64;
+        (negative, trimmed.to_owned(), exponent)
+    }
+
+    // Parses into a Rust number type, e.g. `number.parse::<u128>()`.
+    pub fn parse<T>(&self) -> Result<T, ConversionError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        parse_number(&self.0, ConversionErrorKind::ParseNumber)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidNumber(pub String);
+
+impl std::fmt::Display for InvalidNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a valid DynamoDB number", self.0)
+    }
+}
+
+impl std::error::Error for InvalidNumber {}
+
+fn is_valid_number(s: &str) -> bool {
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let (mantissa, exponent) = match s.find(&['e', 'E'][..]) {
+        Some(index) => (&s[..index], Some(&s[index + 1..])),
+        None => (s, None),
+    };
+    if let Some(exponent) = exponent {
+        let exponent = exponent
+            .strip_prefix('-')
+            .or_else(|| exponent.strip_prefix('+'))
+            .unwrap_or(exponent);
+        if exponent.is_empty() || !exponent.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+        // Far beyond DynamoDB's range of 1E-130 to 1E+126.
+        if exponent.parse::<i32>().is_err() {
+            return false;
+        }
+    }
+    let (integer, fraction) = match mantissa.find('.') {
+        Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+        None => (mantissa, ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return false;
+    }
+    if !integer
+        .bytes()
+        .chain(fraction.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return false;
+    }
+    let digits: String = integer.chars().chain(fraction.chars()).collect();
+    let significant = digits.trim_start_matches('0').trim_end_matches('0');
+    significant.len() <= MAX_PRECISION
+}
+
+impl std::str::FromStr for Number {
+    type Err = InvalidNumber;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !is_valid_number(s) {
+            return Err(InvalidNumber(s.to_owned()));
+        }
+        Ok(Number(s.to_owned()))
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+impl Eq for Number {}
+
+impl std::hash::Hash for Number {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state)
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! number_from_int {
+    ($from: ty) => {
+        impl From<$from> for Number {
+            fn from(value: $from) -> Self {
+                Number(value.to_string())
+            }
+        }
+    };
+}
+
+number_from_int!(usize);
+number_from_int!(u64);
+number_from_int!(u32);
+number_from_int!(u16);
+number_from_int!(u8);
+
+number_from_int!(isize);
+number_from_int!(i64);
+number_from_int!(i32);
+number_from_int!(i16);
+number_from_int!(i8);
+
+// 128-bit integers may have more significant digits than DynamoDB keeps.
+macro_rules! number_try_from_int {
+    ($from: ty) => {
+        impl std::convert::TryFrom<$from> for Number {
+            type Error = InvalidNumber;
+
+            fn try_from(value: $from) -> Result<Self, Self::Error> {
+                value.to_string().parse()
+            }
+        }
+    };
+}
+
+number_try_from_int!(u128);
+number_try_from_int!(i128);
+
+impl IntoAttribute for Number {
+    fn into_attr(self) -> AttributeValue {
+        AttributeValue {
+            n: Some(self.0),
+            ..AttributeValue::default()
+        }
+    }
+}
+
+impl FromAttribute for Number {
+    fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        let n = take_attr(value, AttributeType::N, |value| value.n.take())?;
+        parse_number(&n, ConversionErrorKind::ParseNumber)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+mod decimal_impl {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn parse_decimal(s: &str) -> Result<Decimal, ConversionError> {
+        let parsed = if s.contains(&['e', 'E'][..]) {
+            Decimal::from_scientific(s)
+        } else {
+            s.parse()
+        };
+        parsed.map_err(|e| {
+            ConversionError::new(ConversionErrorKind::ParseNumber)
+                .with_expected(AttributeType::N)
+                .with_cause(e)
+        })
+    }
+
+    impl IntoAttribute for Decimal {
+        fn into_attr(self) -> AttributeValue {
+            AttributeValue {
+                n: Some(self.to_string()),
+                ..AttributeValue::default()
+            }
+        }
+    }
+
+    impl FromAttribute for Decimal {
+        fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+            let n = take_attr(value, AttributeType::N, |value| value.n.take())?;
+            parse_decimal(&n)
+        }
+    }
+
+    impl From<Decimal> for Number {
+        fn from(value: Decimal) -> Self {
+            Number(value.to_string())
+        }
+    }
+
+    impl std::convert::TryFrom<Number> for Decimal {
+        type Error = ConversionError;
+
+        fn try_from(value: Number) -> Result<Self, Self::Error> {
+            parse_decimal(value.as_str())
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+// Mirrors rusoto's `DeleteItemOutput`, without the attributes `delete` never asks for.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DeleteOutput {
+    pub consumed_capacity: Option<crate::ConsumedCapacity>,
+    pub item_collection_metrics: Option<crate::ItemCollectionMetrics>,
+}
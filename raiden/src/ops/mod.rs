@@ -1,5 +1,6 @@
 pub mod batch_delete;
 pub mod batch_get;
+pub mod delete;
 pub mod get;
 pub mod put;
 pub mod query;
@@ -1,17 +1,43 @@
-use crate::{DynamoDb, TransactWriteItem};
+use crate::{AttributeValues, TransactWriteItem};
+
+// Mirrors rusoto's `TransactWriteItemsOutput`.
+#[derive(Default, Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TransactWriteOutput {
+    pub consumed_capacity: Option<Vec<crate::ConsumedCapacity>>,
+    pub item_collection_metrics:
+        Option<std::collections::HashMap<String, Vec<crate::ItemCollectionMetrics>>>,
+}
 
 pub struct WriteTx {
     items: Vec<crate::TransactWriteItem>,
-    client: crate::DynamoDbClient,
+    // Table name and key of each item, to tie cancellation reasons back to them.
+    targets: Vec<(String, Option<AttributeValues>)>,
+    client: crate::Client,
+    region: crate::Region,
+    return_all_old_on_failure: bool,
+    return_consumed_capacity: Option<crate::ReturnConsumedCapacity>,
+    capacity_accumulator: Option<crate::CapacityAccumulator>,
+    metrics: std::sync::Arc<dyn crate::RaidenMetrics>,
+    pipeline: crate::Pipeline,
     retry_condition: crate::RetryCondition,
+    policy: Option<crate::Policy>,
+    error: Option<crate::RaidenError>,
 }
 impl WriteTx {
     pub fn new(region: crate::Region) -> Self {
-        let client = crate::DynamoDbClient::new(region);
         Self {
             items: vec![],
-            client,
+            targets: vec![],
+            client: crate::request_id::shared_client(),
+            region,
+            return_all_old_on_failure: false,
+            return_consumed_capacity: None,
+            capacity_accumulator: None,
+            metrics: std::sync::Arc::new(crate::NoopMetrics),
+            pipeline: crate::Pipeline::new(),
             retry_condition: crate::RetryCondition::new(),
+            policy: None,
+            error: None,
         }
     }
 
@@ -20,89 +46,346 @@ impl WriteTx {
         self
     }
 
+    // Overrides the retry strategy's policy for this transaction.
+    pub fn retry(mut self, policy: crate::Policy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    // Calls `f` with the number of the failed attempt, its error and the delay before each retry.
+    pub fn on_retry(
+        mut self,
+        f: impl Fn(usize, &crate::RaidenError, std::time::Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_condition.on_retry(f);
+        self
+    }
+
+    // Asks DynamoDB to report the capacity consumed by the transaction.
+    pub fn return_consumed_capacity(mut self, value: crate::ReturnConsumedCapacity) -> Self {
+        self.return_consumed_capacity = Some(value);
+        self
+    }
+
+    // Records the capacity consumed by the transaction into `accumulator`.
+    pub fn with_capacity_accumulator(mut self, accumulator: crate::CapacityAccumulator) -> Self {
+        self.capacity_accumulator = Some(accumulator);
+        self
+    }
+
+    // Reports the transaction to `metrics` once it's done.
+    pub fn with_metrics(mut self, metrics: impl crate::RaidenMetrics + 'static) -> Self {
+        self.metrics = std::sync::Arc::new(metrics);
+        self
+    }
+
+    // Passes the request of this transaction through `layer`, after the layers added before.
+    pub fn layer(mut self, layer: impl crate::Layer + 'static) -> Self {
+        self.pipeline.push(layer);
+        self
+    }
+
+    // Returns each item whose condition failed as it was, in `RaidenError::failed_items`.
+    pub fn return_all_old_on_condition_check_failure(mut self) -> Self {
+        self.return_all_old_on_failure = true;
+        self
+    }
+
     pub fn put(mut self, builder: impl TransactWritePutBuilder) -> Self {
-        self.items.push(TransactWriteItem {
-            condition_check: None,
-            delete: None,
-            update: None,
-            put: Some(builder.build()),
-        });
+        let key_names = builder.key_names();
+        match builder.build() {
+            Ok(item) => {
+                let key = if key_names.is_empty() {
+                    None
+                } else {
+                    Some(
+                        item.item
+                            .iter()
+                            .filter(|(name, _)| key_names.contains(name))
+                            .map(|(name, value)| (name.clone(), value.clone()))
+                            .collect(),
+                    )
+                };
+                self.push(
+                    item.table_name.clone(),
+                    key,
+                    TransactWriteItem {
+                        condition_check: None,
+                        delete: None,
+                        update: None,
+                        put: Some(item),
+                    },
+                )
+            }
+            Err(e) => self.fail(e),
+        }
         self
     }
 
     pub fn update(mut self, builder: impl TransactWriteUpdateBuilder) -> Self {
-        self.items.push(TransactWriteItem {
-            condition_check: None,
-            delete: None,
-            update: Some(builder.build()),
-            put: None,
-        });
+        match builder.build() {
+            Ok(item) => self.push(
+                item.table_name.clone(),
+                Some(item.key.clone()),
+                TransactWriteItem {
+                    condition_check: None,
+                    delete: None,
+                    update: Some(item),
+                    put: None,
+                },
+            ),
+            Err(e) => self.fail(e),
+        }
         self
     }
 
     pub fn delete(mut self, builder: impl TransactWriteDeleteBuilder) -> Self {
-        self.items.push(TransactWriteItem {
-            condition_check: None,
-            delete: Some(builder.build()),
-            update: None,
-            put: None,
-        });
+        match builder.build() {
+            Ok(item) => self.push(
+                item.table_name.clone(),
+                Some(item.key.clone()),
+                TransactWriteItem {
+                    condition_check: None,
+                    delete: Some(item),
+                    update: None,
+                    put: None,
+                },
+            ),
+            Err(e) => self.fail(e),
+        }
         self
     }
 
     pub fn condition_check(mut self, builder: impl TransactWriteConditionCheckBuilder) -> Self {
-        self.items.push(TransactWriteItem {
-            condition_check: Some(builder.build()),
-            delete: None,
-            update: None,
-            put: None,
-        });
+        match builder.build() {
+            Ok(item) => self.push(
+                item.table_name.clone(),
+                Some(item.key.clone()),
+                TransactWriteItem {
+                    condition_check: Some(item),
+                    delete: None,
+                    update: None,
+                    put: None,
+                },
+            ),
+            Err(e) => self.fail(e),
+        }
         self
     }
 
-    pub async fn run(self) -> Result<(), crate::RaidenError> {
-        let policy: crate::RetryPolicy = self.retry_condition.strategy.policy().into();
+    fn push(&mut self, table_name: String, key: Option<AttributeValues>, item: TransactWriteItem) {
+        self.targets.push((table_name, key));
+        self.items.push(item);
+    }
+
+    // Keeps the first error raised while building items and reports it on `run`.
+    fn fail(&mut self, error: crate::RaidenError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    pub async fn run(self) -> Result<TransactWriteOutput, crate::RaidenError> {
+        self.run_with_context()
+            .await
+            .map_err(crate::RaidenError::from)
+    }
+
+    // Same as `run`, but the error tells where it happened.
+    pub async fn run_with_context(self) -> Result<TransactWriteOutput, crate::ErrorWithContext> {
+        let context = crate::ErrorContext::new(crate::Operation::TransactWriteItems);
+        let span = crate::OperationSpan::new(&context)
+            .with_table_names(
+                self.targets
+                    .iter()
+                    .map(|(table_name, _)| table_name.as_str()),
+            )
+            .with_item_count(self.items.len());
+        let metrics = self.metrics.clone();
+        let attempts = crate::Attempts::default();
+        let request_id = crate::request_id::RequestIdSlot::default();
+        let result = span
+            .instrument(request_id.scope(self.execute(&attempts)))
+            .await;
+        span.finish(attempts.get(), &result);
+        metrics.record(&crate::OperationMetrics::new(
+            &context,
+            &result,
+            attempts.retries(),
+            span.elapsed(),
+        ));
+        result.map_err(|e| {
+            e.with_context(
+                context
+                    .with_attempts(attempts.get())
+                    .with_request_id(request_id.get()),
+            )
+        })
+    }
+
+    async fn execute(
+        mut self,
+        attempts: &crate::Attempts,
+    ) -> Result<TransactWriteOutput, crate::RaidenError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.return_all_old_on_failure {
+            for item in self.items.iter_mut() {
+                let all_old = Some("ALL_OLD".to_owned());
+                if let Some(put) = item.put.as_mut() {
+                    put.return_values_on_condition_check_failure = all_old;
+                } else if let Some(update) = item.update.as_mut() {
+                    update.return_values_on_condition_check_failure = all_old;
+                } else if let Some(delete) = item.delete.as_mut() {
+                    delete.return_values_on_condition_check_failure = all_old;
+                } else if let Some(condition_check) = item.condition_check.as_mut() {
+                    condition_check.return_values_on_condition_check_failure = all_old;
+                }
+            }
+        }
+        let policy = self
+            .policy
+            .unwrap_or_else(|| self.retry_condition.strategy.policy());
         let client = self.client;
+        let region = self.region;
+        let pipeline = &self.pipeline;
         let input = crate::TransactWriteItemsInput {
             client_request_token: None,
-            return_consumed_capacity: None,
+            return_consumed_capacity: crate::ReturnConsumedCapacity::resolve(
+                self.return_consumed_capacity,
+                self.capacity_accumulator.as_ref(),
+            ),
             return_item_collection_metrics: None,
             transact_items: self.items,
         };
-        policy
-            .retry_if(
-                move || {
-                    let client = client.clone();
-                    let input = input.clone();
-                    async { WriteTx::inner_run(client, input).await }
-                },
-                &self.retry_condition,
-            )
+        let targets = self.targets;
+        let capacity_accumulator = self.capacity_accumulator;
+        let output = self
+            .retry_condition
+            .retry(policy, attempts, move || {
+                let client = client.clone();
+                let region = region.clone();
+                let input = input.clone();
+                async move { WriteTx::inner_run(pipeline, client, region, input).await }
+            })
             .await
+            .map_err(|e| match e {
+                crate::RaidenError::TransactionCanceled {
+                    reasons,
+                    failed_items,
+                } => crate::RaidenError::TransactionCanceled {
+                    reasons,
+                    failed_items: failed_items
+                        .into_iter()
+                        .map(|mut failed| {
+                            if let Some((table_name, key)) = targets.get(failed.index) {
+                                failed.table_name = Some(table_name.clone());
+                                failed.key = key.clone();
+                            }
+                            failed
+                        })
+                        .collect(),
+                },
+                e => e,
+            })?;
+        if let Some(accumulator) = capacity_accumulator {
+            accumulator.record_all(&output.consumed_capacity);
+        }
+        Ok(TransactWriteOutput {
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        })
     }
 
     async fn inner_run(
-        client: crate::DynamoDbClient,
+        pipeline: &crate::Pipeline,
+        client: crate::Client,
+        region: crate::Region,
         input: crate::TransactWriteItemsInput,
-    ) -> Result<(), crate::RaidenError> {
-        let _res = client.transact_write_items(input).await?;
-        // TODO: ADD Resp
-        Ok(())
+    ) -> Result<crate::TransactWriteItemsOutput, crate::RaidenError> {
+        let operation = crate::Operation::TransactWriteItems;
+        let response = pipeline
+            .send(
+                crate::Request::TransactWriteItems(input),
+                |request| async move {
+                    match request {
+                        crate::Request::TransactWriteItems(input) => {
+                            WriteTx::dispatch(client, region, input)
+                                .await
+                                .map(crate::Response::TransactWriteItems)
+                        }
+                        request => Err(crate::middleware::unexpected_operation(
+                            operation,
+                            request.operation(),
+                        )),
+                    }
+                },
+            )
+            .await?;
+        match response {
+            crate::Response::TransactWriteItems(output) => Ok(output),
+            response => Err(crate::middleware::unexpected_operation(
+                operation,
+                response.operation(),
+            )),
+        }
+    }
+
+    // Sent without `DynamoDbClient`, whose errors drop the `CancellationReasons` of the response.
+    async fn dispatch(
+        client: crate::Client,
+        region: crate::Region,
+        input: crate::TransactWriteItemsInput,
+    ) -> Result<crate::TransactWriteItemsOutput, crate::RaidenError> {
+        let mut request = crate::signature::SignedRequest::new("POST", "dynamodb", &region, "/");
+        request.set_content_type("application/x-amz-json-1.0".to_owned());
+        request.add_header("x-amz-target", "DynamoDB_20120810.TransactWriteItems");
+        let payload = serde_json::to_vec(&input)
+            .map_err(|e| crate::RaidenError::ParseError(e.to_string()))?;
+        request.set_payload(Some(payload));
+
+        let response = client
+            .sign_and_dispatch(request)
+            .await
+            .map_err(crate::RusotoError::<crate::TransactWriteItemsError>::from)?;
+        let response = response
+            .buffer()
+            .await
+            .map_err(crate::RaidenError::HttpDispatch)?;
+        if response.status.is_success() {
+            return serde_json::from_slice(&response.body)
+                .map_err(|e| crate::RaidenError::ParseError(e.to_string()));
+        }
+        if let Some((reasons, failed_items)) =
+            crate::RaidenTransactionCancellationReasons::from_response_body(&response.body)
+        {
+            return Err(crate::RaidenError::TransactionCanceled {
+                reasons,
+                failed_items,
+            });
+        }
+        Err(crate::TransactWriteItemsError::from_response(response).into())
     }
 }
 
 pub trait TransactWritePutBuilder {
-    fn build(self) -> crate::Put;
+    fn build(self) -> Result<crate::Put, crate::RaidenError>;
+
+    // Attribute names of the item's key, used to report which item canceled a transaction.
+    fn key_names(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 pub trait TransactWriteUpdateBuilder {
-    fn build(self) -> crate::Update;
+    fn build(self) -> Result<crate::Update, crate::RaidenError>;
 }
 
 pub trait TransactWriteDeleteBuilder {
-    fn build(self) -> crate::Delete;
+    fn build(self) -> Result<crate::Delete, crate::RaidenError>;
 }
 
 pub trait TransactWriteConditionCheckBuilder {
-    fn build(self) -> crate::ConditionCheck;
+    fn build(self) -> Result<crate::ConditionCheck, crate::RaidenError>;
 }
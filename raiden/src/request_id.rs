@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::request::{DispatchSignedRequest, DispatchSignedRequestFuture, HttpClient};
+use crate::signature::SignedRequest;
+
+thread_local! {
+    // The slot of the operation being polled on this thread.
+    static CURRENT: RefCell<Option<RequestIdSlot>> = const { RefCell::new(None) };
+}
+
+// The request id of the last response received by an operation.
+#[derive(Debug, Clone, Default)]
+pub struct RequestIdSlot(Arc<Mutex<Option<String>>>);
+
+impl RequestIdSlot {
+    pub fn get(&self) -> Option<String> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn set(&self, request_id: String) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(request_id);
+    }
+
+    // Records the request ids of the responses received while `future` is polled.
+    pub fn scope<F: Future>(&self, future: F) -> Scoped<F> {
+        Scoped {
+            slot: self.clone(),
+            future: Box::pin(future),
+        }
+    }
+}
+
+pub struct Scoped<F> {
+    slot: RequestIdSlot,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let previous = CURRENT.with(|current| current.replace(Some(self.slot.clone())));
+        let poll = self.future.as_mut().poll(cx);
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+        poll
+    }
+}
+
+// Reads `x-amzn-requestid` before rusoto parses the response, since its typed errors drop the headers.
+pub struct RequestIdDispatcher<D>(pub D);
+
+impl<D: DispatchSignedRequest> DispatchSignedRequest for RequestIdDispatcher<D> {
+    fn dispatch(
+        &self,
+        request: SignedRequest,
+        timeout: Option<std::time::Duration>,
+    ) -> DispatchSignedRequestFuture {
+        let response = self.0.dispatch(request, timeout);
+        Box::pin(async move {
+            let response = response.await?;
+            if let Some(request_id) = response.headers.get("x-amzn-requestid") {
+                CURRENT.with(|current| {
+                    if let Some(slot) = current.borrow().as_ref() {
+                        slot.set(request_id.clone());
+                    }
+                });
+            }
+            Ok(response)
+        })
+    }
+}
+
+// The rusoto client shared by generated clients, like `Client::shared` but recording request ids.
+pub fn shared_client() -> crate::Client {
+    static SHARED: Mutex<Option<crate::Client>> = Mutex::new(None);
+
+    SHARED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(|| {
+            let credentials = crate::DefaultCredentialsProvider::new()
+                .expect("failed to create credentials provider");
+            let dispatcher = HttpClient::new().expect("failed to create request dispatcher");
+            crate::Client::new_with(credentials, RequestIdDispatcher(dispatcher))
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slot_is_set_within_scope() {
+        let slot = RequestIdSlot::default();
+        slot.scope(async {
+            CURRENT.with(|current| current.borrow().as_ref().unwrap().set("id0".to_owned()));
+        })
+        .await;
+        assert_eq!(slot.get(), Some("id0".to_owned()));
+        assert!(CURRENT.with(|current| current.borrow().is_none()));
+    }
+}
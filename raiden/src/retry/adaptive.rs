@@ -0,0 +1,300 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use wasm_timer::Instant;
+
+use super::{Policy, RetryStrategy};
+use crate::RaidenError;
+
+// Client side rate limiting in the style of the AWS SDKs' adaptive retry mode.
+// Requests are sent freely until the first throttle. From then on they are paced by a token
+// bucket, whose rate is cut below the measured send rate on each throttle and raised again
+// along a cubic curve, as in CUBIC congestion control, on each success. Clones share the bucket.
+#[derive(Debug, Clone)]
+pub struct AdaptiveRateLimiter {
+    limiter: Arc<Mutex<Limiter>>,
+}
+
+#[derive(Debug)]
+struct Limiter {
+    enabled: bool,
+    // Requests per second.
+    rate: f64,
+    min_rate: f64,
+    max_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+    measured_rate: f64,
+    sent: usize,
+    window_start: Instant,
+    // Rate at the last throttle, which the cubic curve climbs back to.
+    last_max_rate: f64,
+    last_throttle: Instant,
+}
+
+// Rate kept after a throttle.
+const BETA: f64 = 0.7;
+// How fast the rate grows back after a throttle.
+const SCALE: f64 = 0.4;
+// Floor of the configured rates, since a request waits for `1 / rate` seconds.
+const MIN_FILL_RATE: f64 = 0.01;
+
+impl Default for AdaptiveRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdaptiveRateLimiter {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            limiter: Arc::new(Mutex::new(Limiter {
+                enabled: false,
+                rate: f64::INFINITY,
+                min_rate: 0.5,
+                max_rate: f64::INFINITY,
+                tokens: 0.0,
+                last_refill: now,
+                measured_rate: 0.0,
+                sent: 0,
+                window_start: now,
+                last_max_rate: 0.0,
+                last_throttle: now,
+            })),
+        }
+    }
+
+    // Upper bound of requests per second once throttled, unbounded by default.
+    // Rates below 0.01, including zero, are raised to it.
+    pub fn with_max_rate(self, max_rate: f64) -> Self {
+        self.lock().max_rate = max_rate.max(MIN_FILL_RATE);
+        self
+    }
+
+    // Lower bound of requests per second, 0.5 by default. Raised to 0.01 like `with_max_rate`.
+    pub fn with_min_rate(self, min_rate: f64) -> Self {
+        self.lock().min_rate = min_rate.max(MIN_FILL_RATE);
+        self
+    }
+
+    // Requests per second allowed now, or `None` before the first throttle.
+    pub fn rate(&self) -> Option<f64> {
+        let limiter = self.lock();
+        if limiter.enabled {
+            Some(limiter.rate)
+        } else {
+            None
+        }
+    }
+
+    // Takes a token for a request and returns how long to wait before sending it.
+    pub fn acquire(&self) -> Duration {
+        let mut limiter = self.lock();
+        let now = Instant::now();
+        limiter.measure(now);
+        if !limiter.enabled {
+            return Duration::from_millis(0);
+        }
+        limiter.refill(now);
+        // Tokens may go negative, which reserves a slot for concurrent requests.
+        limiter.tokens -= 1.0;
+        if limiter.tokens >= 0.0 {
+            Duration::from_millis(0)
+        } else {
+            Duration::from_secs_f64(-limiter.tokens / limiter.rate)
+        }
+    }
+
+    pub fn on_throttle(&self) {
+        let mut limiter = self.lock();
+        let now = Instant::now();
+        let base = match (limiter.enabled, limiter.send_rate(now)) {
+            (true, Some(send_rate)) => limiter.rate.min(send_rate),
+            (true, None) => limiter.rate,
+            (false, Some(send_rate)) => send_rate,
+            // Nothing was sent to tell how far to cut the rate.
+            (false, None) => return,
+        };
+        limiter.refill(now);
+        limiter.rate = (base * BETA).max(limiter.min_rate).min(limiter.max_rate);
+        limiter.tokens = limiter.tokens.min(0.0);
+        limiter.enabled = true;
+        limiter.last_max_rate = base;
+        limiter.last_throttle = now;
+    }
+
+    pub fn on_success(&self) {
+        let mut limiter = self.lock();
+        if limiter.enabled {
+            // Climbs quickly back to the rate of the last throttle, then slowly probes above it.
+            let elapsed = Instant::now()
+                .duration_since(limiter.last_throttle)
+                .as_secs_f64();
+            let k = (limiter.last_max_rate * (1.0 - BETA) / SCALE).cbrt();
+            let rate = SCALE * (elapsed - k).powi(3) + limiter.last_max_rate;
+            limiter.rate = rate.max(limiter.min_rate).min(limiter.max_rate);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Limiter> {
+        self.limiter.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Limiter {
+    fn measure(&mut self, now: Instant) {
+        self.sent += 1;
+        let elapsed = now.duration_since(self.window_start).as_secs_f64();
+        if elapsed >= 1.0 {
+            let rate = self.sent as f64 / elapsed;
+            self.measured_rate = if self.measured_rate == 0.0 {
+                rate
+            } else {
+                self.measured_rate * 0.8 + rate * 0.2
+            };
+            self.sent = 0;
+            self.window_start = now;
+        }
+    }
+
+    // Requests per second sent lately. The current window seeds it until the first one is over.
+    fn send_rate(&self, now: Instant) -> Option<f64> {
+        if self.measured_rate > 0.0 {
+            return Some(self.measured_rate);
+        }
+        let elapsed = now.duration_since(self.window_start).as_secs_f64();
+        if self.sent == 0 || elapsed == 0.0 {
+            None
+        } else {
+            Some(self.sent as f64 / elapsed)
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate.max(1.0));
+        self.last_refill = now;
+    }
+}
+
+// A strategy whose requests are paced by an `AdaptiveRateLimiter`.
+pub struct Adaptive<S> {
+    inner: S,
+    limiter: AdaptiveRateLimiter,
+}
+
+impl<S: RetryStrategy> Adaptive<S> {
+    pub fn new(inner: S, limiter: AdaptiveRateLimiter) -> Self {
+        Self { inner, limiter }
+    }
+
+    pub fn limiter(&self) -> &AdaptiveRateLimiter {
+        &self.limiter
+    }
+}
+
+impl<S: RetryStrategy> RetryStrategy for Adaptive<S> {
+    fn should_retry(&self, error: &RaidenError) -> bool {
+        self.inner.should_retry(error)
+    }
+
+    fn policy(&self) -> Policy {
+        self.inner.policy()
+    }
+
+    fn acquire_retry(&self, error: &RaidenError) -> bool {
+        self.inner.acquire_retry(error)
+    }
+
+    fn delay_before_send(&self) -> Duration {
+        std::cmp::max(self.inner.delay_before_send(), self.limiter.acquire())
+    }
+
+    fn on_response(&self, result: Result<(), &RaidenError>) {
+        match result {
+            Ok(()) => self.limiter.on_success(),
+            Err(error) if error.is_throttling() => self.limiter.on_throttle(),
+            Err(_) => {}
+        }
+        self.inner.on_response(result)
+    }
+
+    fn before_retry(&self, attempt: usize, error: &RaidenError, delay: Duration) {
+        self.inner.before_retry(attempt, error, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_limiter_is_disabled_until_throttled() {
+        let limiter = AdaptiveRateLimiter::new();
+        assert_eq!(limiter.rate(), None);
+        assert_eq!(limiter.acquire(), Duration::from_millis(0));
+        limiter.on_success();
+        assert_eq!(limiter.rate(), None);
+    }
+
+    #[test]
+    fn test_limiter_paces_requests_after_throttle() {
+        let limiter = AdaptiveRateLimiter::new()
+            .with_min_rate(2.0)
+            .with_max_rate(2.0);
+        limiter.acquire();
+        limiter.on_throttle();
+        assert_eq!(limiter.rate(), Some(2.0));
+        // The bucket is empty right after a throttle, so each request waits for its token.
+        let first = limiter.acquire();
+        let second = limiter.acquire();
+        assert!(first > Duration::from_millis(0));
+        assert!(second > first);
+    }
+
+    #[test]
+    fn test_limiter_raises_non_positive_rates() {
+        let limiter = AdaptiveRateLimiter::new()
+            .with_min_rate(0.0)
+            .with_max_rate(0.0);
+        limiter.acquire();
+        limiter.on_throttle();
+        assert_eq!(limiter.rate(), Some(MIN_FILL_RATE));
+        assert!(limiter.acquire() > Duration::from_secs(0));
+
+        let limiter = AdaptiveRateLimiter::new().with_max_rate(-1.0);
+        limiter.acquire();
+        limiter.on_throttle();
+        assert_eq!(limiter.rate(), Some(MIN_FILL_RATE));
+        limiter.acquire();
+    }
+
+    #[test]
+    fn test_limiter_recovers_on_success() {
+        let limiter = AdaptiveRateLimiter::new();
+        limiter.lock().measured_rate = 10.0;
+        limiter.on_throttle();
+        assert!((limiter.rate().unwrap() - 7.0).abs() < 1e-6);
+        limiter.on_success();
+        assert!((limiter.rate().unwrap() - 7.0).abs() < 1e-3);
+
+        limiter.lock().last_throttle = Instant::now() - Duration::from_secs(3);
+        limiter.on_success();
+        assert!(limiter.rate().unwrap() > 10.0);
+    }
+
+    #[test]
+    fn test_throttle_right_after_construction() {
+        let limiter = AdaptiveRateLimiter::new();
+        limiter.on_throttle();
+        assert_eq!(limiter.rate(), None);
+
+        limiter.acquire();
+        limiter.on_throttle();
+        // Measured over the first, partial window instead of cutting to the minimum rate.
+        assert!(limiter.rate().unwrap() > 0.5);
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::{Policy, RetryStrategy};
+use crate::RaidenError;
+
+// A token bucket limiting retries. Clones share the bucket, so one budget can be shared by
+// a client, its clones and other clients. Each retry withdraws `retry_cost` tokens and each
+// successful request deposits one back, so retries stop once most requests fail, e.g. during
+// a throttling storm, and resume as requests succeed again.
+#[derive(Debug, Clone)]
+pub struct RetryBudget {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: usize,
+    available: usize,
+    retry_cost: usize,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl RetryBudget {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                available: capacity,
+                retry_cost: 5,
+            })),
+        }
+    }
+
+    // Tokens withdrawn per retry, 5 by default.
+    pub fn with_retry_cost(self, retry_cost: usize) -> Self {
+        self.lock().retry_cost = retry_cost;
+        self
+    }
+
+    pub fn available(&self) -> usize {
+        self.lock().available
+    }
+
+    // Withdraws the cost of a retry, or returns `false` if the budget can't afford it.
+    pub fn try_withdraw(&self) -> bool {
+        let mut bucket = self.lock();
+        match bucket.available.checked_sub(bucket.retry_cost) {
+            Some(available) => {
+                bucket.available = available;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn deposit(&self) {
+        let mut bucket = self.lock();
+        bucket.available = std::cmp::min(bucket.capacity, bucket.available + 1);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// A strategy whose retries are paid from a `RetryBudget`.
+pub struct WithRetryBudget<S> {
+    inner: S,
+    budget: RetryBudget,
+}
+
+impl<S: RetryStrategy> WithRetryBudget<S> {
+    pub fn new(inner: S, budget: RetryBudget) -> Self {
+        Self { inner, budget }
+    }
+
+    pub fn budget(&self) -> &RetryBudget {
+        &self.budget
+    }
+}
+
+impl<S: RetryStrategy> RetryStrategy for WithRetryBudget<S> {
+    fn should_retry(&self, error: &RaidenError) -> bool {
+        self.inner.should_retry(error)
+    }
+
+    fn policy(&self) -> Policy {
+        self.inner.policy()
+    }
+
+    fn acquire_retry(&self, error: &RaidenError) -> bool {
+        self.inner.acquire_retry(error) && self.budget.try_withdraw()
+    }
+
+    fn delay_before_send(&self) -> Duration {
+        self.inner.delay_before_send()
+    }
+
+    fn on_response(&self, result: Result<(), &RaidenError>) {
+        if result.is_ok() {
+            self.budget.deposit();
+        }
+        self.inner.on_response(result)
+    }
+
+    fn before_retry(&self, attempt: usize, error: &RaidenError, delay: Duration) {
+        self.inner.before_retry(attempt, error, delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_budget_is_exhausted_by_retries() {
+        let budget = RetryBudget::new(10);
+        assert!(budget.try_withdraw());
+        assert!(budget.try_withdraw());
+        assert!(!budget.try_withdraw());
+        assert_eq!(budget.available(), 0);
+    }
+
+    #[test]
+    fn test_budget_is_refilled_up_to_capacity() {
+        let budget = RetryBudget::new(10).with_retry_cost(4);
+        assert!(budget.try_withdraw());
+        for _ in 0..10 {
+            budget.deposit();
+        }
+        assert_eq!(budget.available(), 10);
+    }
+
+    #[test]
+    fn test_budget_is_shared_by_clones() {
+        let budget = RetryBudget::new(5);
+        let cloned = budget.clone();
+        assert!(cloned.try_withdraw());
+        assert!(!budget.try_withdraw());
+    }
+}
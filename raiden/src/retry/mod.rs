@@ -1,7 +1,14 @@
 pub use again::{Condition, RetryPolicy};
+use std::future::Future;
 use std::time::Duration;
 
-use super::RaidenError;
+use super::{Attempts, RaidenError};
+
+mod adaptive;
+mod budget;
+
+pub use adaptive::*;
+pub use budget::*;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Policy {
@@ -35,20 +42,115 @@ impl Into<RetryPolicy> for Policy {
     }
 }
 
+impl Policy {
+    // Delay before the `retry`th retry (0 origin), or `None` when retries are exhausted.
+    // The schedule is the one of `RetryPolicy` converted from this policy, with full jitter.
+    pub fn backoff(&self, retry: usize) -> Option<Duration> {
+        let delay = match *self {
+            Policy::None => return None,
+            Policy::Limit(times) | Policy::Pause(times, _) | Policy::Exponential(times, _)
+                if retry >= times =>
+            {
+                return None
+            }
+            Policy::Pause(_, duration) => duration,
+            // `RetryPolicy::default()` backs off exponentially from a second.
+            Policy::Limit(_) => exponential(Duration::from_secs(1), retry),
+            Policy::Exponential(_, duration) => exponential(duration, retry),
+        };
+        Some(delay.mul_f64(rand::random::<f64>()))
+    }
+}
+
+fn exponential(duration: Duration, retry: usize) -> Duration {
+    duration.saturating_mul(2u32.saturating_pow(retry.min(31) as u32))
+}
+
+pub async fn sleep(duration: Duration) {
+    if duration > Duration::from_millis(0) {
+        let _ = wasm_timer::Delay::new(duration).await;
+    }
+}
+
+type RetryHook = Box<dyn Fn(usize, &RaidenError, Duration) + Send + Sync>;
+
 pub struct RetryCondition {
     pub strategy: Box<dyn RetryStrategy + Send + Sync>,
+    // Kept apart from `strategy`, so replacing it doesn't drop them.
+    hooks: Vec<RetryHook>,
 }
 
 impl RetryCondition {
     pub fn new() -> Self {
         Default::default()
     }
+
+    pub fn with_strategy(strategy: Box<dyn RetryStrategy + Send + Sync>) -> Self {
+        Self {
+            strategy,
+            hooks: vec![],
+        }
+    }
 }
 
 impl Default for RetryCondition {
     fn default() -> Self {
-        Self {
-            strategy: Box::new(DefaultRetryStrategy),
+        Self::with_strategy(Box::new(DefaultRetryStrategy))
+    }
+}
+
+impl RetryCondition {
+    // Calls `f` with the number of the failed attempt (1 origin), its error and the delay
+    // before the next one, each time a request is retried.
+    pub fn on_retry(&mut self, f: impl Fn(usize, &RaidenError, Duration) + Send + Sync + 'static) {
+        self.hooks.push(Box::new(f));
+    }
+
+    // Sends the requests made by `request` until one succeeds, `policy` runs out of retries
+    // or the strategy gives up.
+    pub async fn retry<T, F, Fut>(
+        &self,
+        policy: Policy,
+        attempts: &Attempts,
+        mut request: F,
+    ) -> Result<T, RaidenError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RaidenError>>,
+    {
+        let strategy = &*self.strategy;
+        let mut retry = 0;
+        loop {
+            attempts.start(retry + 1);
+            sleep(strategy.delay_before_send()).await;
+            let response = request().await;
+            strategy.on_response(response.as_ref().map(|_| ()));
+            let error = match response {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+            if !strategy.should_retry(&error) {
+                return Err(error);
+            }
+            // Asking the strategy last keeps a budget from paying for retries that never happen.
+            let delay = match policy.backoff(retry) {
+                Some(delay) if strategy.acquire_retry(&error) => delay,
+                _ => return Err(error),
+            };
+            retry += 1;
+            strategy.before_retry(retry, &error, delay);
+            for hook in &self.hooks {
+                hook(retry, &error, delay);
+            }
+            log::trace!("{} will be retried in {:?}", error, delay);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                attempt = retry as u64,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "retrying DynamoDB request"
+            );
+            sleep(delay).await;
         }
     }
 }
@@ -62,23 +164,71 @@ impl Condition<super::RaidenError> for &RetryCondition {
 pub trait RetryStrategy {
     fn should_retry(&self, error: &RaidenError) -> bool;
     fn policy(&self) -> Policy;
+
+    // Called once `should_retry` allowed a retry. Returning `false` gives up instead,
+    // e.g. when a retry budget is exhausted.
+    fn acquire_retry(&self, _error: &RaidenError) -> bool {
+        true
+    }
+
+    // Delay before each request is sent, used for client side rate limiting.
+    fn delay_before_send(&self) -> Duration {
+        Duration::from_millis(0)
+    }
+
+    // Called with the outcome of each request.
+    fn on_response(&self, _result: Result<(), &RaidenError>) {}
+
+    // Called before sleeping `delay` to retry the `attempt`th request (1 origin) which failed with `error`.
+    fn before_retry(&self, _attempt: usize, _error: &RaidenError, _delay: Duration) {}
+}
+
+impl RetryStrategy for Box<dyn RetryStrategy + Send + Sync> {
+    fn should_retry(&self, error: &RaidenError) -> bool {
+        (**self).should_retry(error)
+    }
+
+    fn policy(&self) -> Policy {
+        (**self).policy()
+    }
+
+    fn acquire_retry(&self, error: &RaidenError) -> bool {
+        (**self).acquire_retry(error)
+    }
+
+    fn delay_before_send(&self) -> Duration {
+        (**self).delay_before_send()
+    }
+
+    fn on_response(&self, result: Result<(), &RaidenError>) {
+        (**self).on_response(result)
+    }
+
+    fn before_retry(&self, attempt: usize, error: &RaidenError, delay: Duration) {
+        (**self).before_retry(attempt, error, delay)
+    }
 }
 
+// Adds a retry budget or adaptive rate limiting to a strategy, e.g.
+// `DefaultRetryStrategy.with_budget(budget.clone()).adaptive(limiter.clone())`.
+pub trait RetryStrategyExt: RetryStrategy + Sized {
+    fn with_budget(self, budget: RetryBudget) -> WithRetryBudget<Self> {
+        WithRetryBudget::new(self, budget)
+    }
+
+    fn adaptive(self, limiter: AdaptiveRateLimiter) -> Adaptive<Self> {
+        Adaptive::new(self, limiter)
+    }
+}
+
+impl<S: RetryStrategy> RetryStrategyExt for S {}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct DefaultRetryStrategy;
 
 impl RetryStrategy for DefaultRetryStrategy {
     fn should_retry(&self, error: &RaidenError) -> bool {
-        matches!(
-            error,
-            RaidenError::InternalServerError(_)
-                | RaidenError::ProvisionedThroughputExceeded(_)
-                | RaidenError::RequestLimitExceeded(_)
-                // INFO: For now, return true, when unknown error detected.
-                //       This is because, sometimes throttlingException is included in unknown error.
-                //       please make more rigorous classification of errors.
-                | RaidenError::Unknown(_)
-        )
+        error.is_retryable()
     }
 
     fn policy(&self) -> Policy {
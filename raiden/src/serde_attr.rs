@@ -0,0 +1,39 @@
+use crate::{AttributeValue, ConversionError, ConversionErrorKind, FromAttribute, IntoAttribute};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// Serde bridge used by `#[raiden(serde)]`. Values go through `serde_json::Value`,
+// so they are stored with the same mapping as a `serde_json::Value` field.
+
+fn serde_error(e: serde_json::Error) -> ConversionError {
+    ConversionError::new(ConversionErrorKind::Serde).with_cause(e)
+}
+
+pub fn to_attr<T: Serialize>(value: &T) -> Result<AttributeValue, ConversionError> {
+    serde_json::to_value(value)
+        .map(IntoAttribute::into_attr)
+        .map_err(serde_error)
+}
+
+pub fn from_attr<T: DeserializeOwned>(value: Option<AttributeValue>) -> Result<T, ConversionError> {
+    let value = serde_json::Value::from_attr(value)?;
+    serde_json::from_value(value).map_err(serde_error)
+}
+
+// Serializes `value` into an item. It has to serialize to a map, e.g. a struct.
+pub fn to_item<T: Serialize>(
+    value: &T,
+) -> Result<std::collections::HashMap<String, AttributeValue>, ConversionError> {
+    to_attr(value)?.m.ok_or_else(|| {
+        ConversionError::new(ConversionErrorKind::Serde).with_expected(crate::AttributeType::M)
+    })
+}
+
+pub fn from_item<T: DeserializeOwned>(
+    item: std::collections::HashMap<String, AttributeValue>,
+) -> Result<T, ConversionError> {
+    from_attr(Some(AttributeValue {
+        m: Some(item),
+        ..AttributeValue::default()
+    }))
+}
@@ -6,7 +6,7 @@ pub struct Add<T: super::IntoAttrName> {
 
 pub struct AddExpressionFilled<T: super::IntoAttrName> {
     target: T,
-    value: (super::Placeholder, super::AttributeValue),
+    value: super::AttributeValue,
 }
 
 impl<T: super::IntoAttrName> Add<T> {
@@ -15,21 +15,23 @@ impl<T: super::IntoAttrName> Add<T> {
     }
 
     pub fn value(self, value: impl super::IntoAttribute) -> AddExpressionFilled<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let value = (placeholder, value.into_attr());
+        let value = value.into_attr();
         let Add { target } = self;
         AddExpressionFilled::<T> { target, value }
     }
 }
 
 impl<T: super::IntoAttrName> UpdateAddExpressionBuilder for AddExpressionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
         let attr = self.target.into_attr_name();
         let attr_name = format!("#{}", attr);
 
         let mut names: super::AttributeNames = std::collections::HashMap::new();
         let mut values: super::AttributeValues = std::collections::HashMap::new();
-        let (placeholder, value) = self.value;
+        let value = self.value;
 
         // See. https://github.com/raiden-rs/raiden/issues/57
         //      https://github.com/raiden-rs/raiden/issues/58
@@ -38,6 +40,7 @@ impl<T: super::IntoAttrName> UpdateAddExpressionBuilder for AddExpressionFilled<
         }
 
         names.insert(attr_name.clone(), attr);
+        let placeholder = placeholders.push(value.clone());
         let expression = format!("{} {}", attr_name, placeholder);
         values.insert(placeholder, value);
         (expression, names, values)
@@ -68,7 +71,6 @@ mod tests {
 
     #[test]
     fn test_add_value_expression() {
-        crate::value_id::reset_value_id();
         let (expression, names, values) = Add::new(UserAttrNames::Age).value(42).build();
         let mut expected_names = std::collections::HashMap::new();
         let mut expected_values = std::collections::HashMap::new();
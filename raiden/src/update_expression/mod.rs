@@ -7,8 +7,8 @@ pub use delete::*;
 pub use set::*;
 
 use super::{
-    generate_value_id, AttributeNames, AttributeValue, AttributeValues, IntoAttrName,
-    IntoAttribute, Placeholder,
+    AttrOrValue, AttributeNames, AttributeValue, AttributeValues, IntoAttrName, IntoAttribute,
+    Placeholders,
 };
 
 pub enum SetOrRemove {
@@ -17,13 +17,188 @@ pub enum SetOrRemove {
 }
 
 pub trait UpdateSetExpressionBuilder {
-    fn build(self) -> SetOrRemove;
+    fn build_with(self, placeholders: &mut Placeholders) -> SetOrRemove;
+
+    fn build(self) -> SetOrRemove
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut Placeholders::new())
+    }
 }
 
 pub trait UpdateAddExpressionBuilder {
-    fn build(self) -> (String, AttributeNames, AttributeValues);
+    fn build_with(
+        self,
+        placeholders: &mut Placeholders,
+    ) -> (String, AttributeNames, AttributeValues);
+
+    fn build(self) -> (String, AttributeNames, AttributeValues)
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut Placeholders::new())
+    }
 }
 
 pub trait UpdateDeleteExpressionBuilder {
-    fn build(self) -> (String, AttributeNames, AttributeValues);
+    fn build_with(
+        self,
+        placeholders: &mut Placeholders,
+    ) -> (String, AttributeNames, AttributeValues);
+
+    fn build(self) -> (String, AttributeNames, AttributeValues)
+    where
+        Self: Sized,
+    {
+        self.build_with(&mut Placeholders::new())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArithmeticOperator {
+    Add,
+    Sub,
+}
+
+// `SET #attr = value` or `SET #attr = value (+|-) operand`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetAction {
+    pub attr: String,
+    pub value: AttrOrValue,
+    pub operation: Option<(ArithmeticOperator, AttrOrValue)>,
+}
+
+// Untyped update expression tree, e.g. produced by `raiden::expr::parse_update`.
+// Actions keep their order and placeholders are allocated on rendering.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateExpr {
+    pub add: Vec<(String, AttributeValue)>,
+    pub set: Vec<SetAction>,
+    pub remove: Vec<String>,
+    pub delete: Vec<(String, AttributeValue)>,
+}
+
+impl SetAction {
+    fn render(&self, placeholders: &mut Placeholders) -> (String, AttributeNames, AttributeValues) {
+        let mut names: AttributeNames = std::collections::HashMap::new();
+        let mut values: AttributeValues = std::collections::HashMap::new();
+        let attr_name = format!("#{}", self.attr);
+        names.insert(attr_name.clone(), self.attr.clone());
+        let value = self.value.render(placeholders, &mut names, &mut values);
+        let expression = match &self.operation {
+            Some((operator, operand)) => {
+                let operand = operand.render(placeholders, &mut names, &mut values);
+                let operator = match operator {
+                    ArithmeticOperator::Add => "+",
+                    ArithmeticOperator::Sub => "-",
+                };
+                format!("{} = {} {} {}", attr_name, value, operator, operand)
+            }
+            None => format!("{} = {}", attr_name, value),
+        };
+        (expression, names, values)
+    }
+}
+
+fn render_value_action(
+    attr: &str,
+    value: &AttributeValue,
+    placeholders: &mut Placeholders,
+) -> (String, AttributeNames, AttributeValues) {
+    let mut names: AttributeNames = std::collections::HashMap::new();
+    let mut values: AttributeValues = std::collections::HashMap::new();
+    let attr_name = format!("#{}", attr);
+    names.insert(attr_name.clone(), attr.to_owned());
+    let placeholder = placeholders.push(value.clone());
+    values.insert(placeholder.clone(), value.clone());
+    (format!("{} {}", attr_name, placeholder), names, values)
+}
+
+pub type UpdateActions = (
+    Vec<(String, AttributeNames, AttributeValues)>,
+    Vec<SetOrRemove>,
+    Vec<(String, AttributeNames, AttributeValues)>,
+);
+
+impl UpdateExpr {
+    // Splits the tree into ADD, SET/REMOVE and DELETE items in the shape the generated update builders collect.
+    pub fn build_actions_with(&self, placeholders: &mut Placeholders) -> UpdateActions {
+        let add = self
+            .add
+            .iter()
+            .map(|(attr, value)| render_value_action(attr, value, placeholders))
+            .collect();
+        let mut set: Vec<SetOrRemove> = self
+            .set
+            .iter()
+            .map(|action| {
+                let (expression, names, values) = action.render(placeholders);
+                SetOrRemove::Set(expression, names, values)
+            })
+            .collect();
+        set.extend(self.remove.iter().map(|attr| {
+            let attr_name = format!("#{}", attr);
+            let mut names: AttributeNames = std::collections::HashMap::new();
+            names.insert(attr_name.clone(), attr.clone());
+            SetOrRemove::Remove(attr_name, names)
+        }));
+        let delete = self
+            .delete
+            .iter()
+            .map(|(attr, value)| render_value_action(attr, value, placeholders))
+            .collect();
+        (add, set, delete)
+    }
+
+    pub fn render(
+        &self,
+        placeholders: &mut Placeholders,
+    ) -> (String, AttributeNames, AttributeValues) {
+        let (add, set, delete) = self.build_actions_with(placeholders);
+        let mut names: AttributeNames = std::collections::HashMap::new();
+        let mut values: AttributeValues = std::collections::HashMap::new();
+        let mut join = |items: Vec<(String, AttributeNames, AttributeValues)>| {
+            items
+                .into_iter()
+                .map(|(expression, n, v)| {
+                    names.extend(n);
+                    values.extend(v);
+                    expression
+                })
+                .collect::<Vec<String>>()
+        };
+        let add = join(add);
+        let delete = join(delete);
+        let mut set_expressions = vec![];
+        let mut remove_expressions = vec![];
+        for item in set {
+            match item {
+                SetOrRemove::Set(expression, n, v) => {
+                    names.extend(n);
+                    values.extend(v);
+                    set_expressions.push(expression);
+                }
+                SetOrRemove::Remove(expression, n) => {
+                    names.extend(n);
+                    remove_expressions.push(expression);
+                }
+            }
+        }
+
+        let mut clauses = vec![];
+        for (keyword, expressions) in [
+            ("ADD", add),
+            ("SET", set_expressions),
+            ("REMOVE", remove_expressions),
+            ("DELETE", delete),
+        ]
+        .iter()
+        {
+            if !expressions.is_empty() {
+                clauses.push(format!("{} {}", keyword, expressions.join(", ")));
+            }
+        }
+        (clauses.join(" "), names, values)
+    }
 }
@@ -39,14 +39,14 @@ impl std::fmt::Display for SetOperation {
 #[allow(clippy::large_enum_variant)]
 enum SetValue<T: super::IntoAttrName> {
     Attr(T),
-    Value(super::Placeholder, super::AttributeValue),
+    Value(super::AttributeValue),
     // ListAppend(T, List)
 }
 
 #[allow(clippy::large_enum_variant)]
 enum Operand<T: super::IntoAttrName> {
     _Attr(T),
-    Value(super::Placeholder, super::AttributeValue),
+    Value(super::AttributeValue),
 }
 
 impl<T: super::IntoAttrName> Set<T> {
@@ -65,8 +65,7 @@ impl<T: super::IntoAttrName> Set<T> {
     }
 
     pub fn value(self, value: impl super::IntoAttribute) -> SetExpressionFilledWithoutOperation<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let value = SetValue::<T>::Value(placeholder, value.into_attr());
+        let value = SetValue::<T>::Value(value.into_attr());
         let Set { target, index, .. } = self;
         SetExpressionFilledWithoutOperation::<T> {
             target,
@@ -95,8 +94,7 @@ impl<T: super::IntoAttrName> SetExpressionFilledWithoutOperation<T> {
     }
 
     pub fn add_value(self, value: impl super::IntoAttribute) -> SetExpressionFilled<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let operand = Operand::<T>::Value(placeholder, value.into_attr());
+        let operand = Operand::<T>::Value(value.into_attr());
         let SetExpressionFilledWithoutOperation {
             target,
             index,
@@ -115,7 +113,7 @@ impl<T: super::IntoAttrName> SetExpressionFilledWithoutOperation<T> {
 }
 
 impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledWithoutOperation<T> {
-    fn build(self) -> SetOrRemove {
+    fn build_with(self, placeholders: &mut super::Placeholders) -> SetOrRemove {
         let attr = self.target.into_attr_name();
         let attr_name = format!("#{}", attr);
 
@@ -130,13 +128,15 @@ impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledW
                 names.insert(set_attr_name, set_attr);
                 SetOrRemove::Set(expression, names, values)
             }
-            SetValue::Value(placeholder, value) => {
+            SetValue::Value(value) => {
                 // See. https://github.com/raiden-rs/raiden/issues/57
                 //      https://github.com/raiden-rs/raiden/issues/58
-                if value == AttributeValue::default() {
+                let value = match self.target.empty_value_policy().apply(value) {
+                    Some(value) => value,
                     // Use remove instead of set
-                    return SetOrRemove::Remove(attr_name, names);
-                }
+                    None => return SetOrRemove::Remove(attr_name, names),
+                };
+                let placeholder = placeholders.push(value.clone());
                 let expression = format!("{} = {}", attr_name, placeholder);
                 values.insert(placeholder, value);
                 SetOrRemove::Set(expression, names, values)
@@ -146,7 +146,7 @@ impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledW
 }
 
 impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilled<T> {
-    fn build(self) -> SetOrRemove {
+    fn build_with(self, placeholders: &mut super::Placeholders) -> SetOrRemove {
         let attr = self.target.into_attr_name();
         let attr_name = format!("#{}", attr);
 
@@ -154,6 +154,20 @@ impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilled<
         names.insert(attr_name.clone(), attr);
         let mut values: super::AttributeValues = std::collections::HashMap::new();
 
+        let value_expression = match self.value {
+            SetValue::Attr(a) => {
+                let set_attr = a.into_attr_name();
+                let set_attr_name = format!("#{}", set_attr);
+                names.insert(set_attr_name.clone(), set_attr);
+                set_attr_name
+            }
+            SetValue::Value(value) => {
+                let placeholder = placeholders.push(value.clone());
+                values.insert(placeholder.clone(), value);
+                placeholder
+            }
+        };
+
         let op = format!("{}", self.operation);
         let op_expression = match self.operand {
             Operand::_Attr(a) => {
@@ -163,27 +177,16 @@ impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilled<
                 names.insert(operand_attr_name, operand_attr);
                 val
             }
-            Operand::Value(placeholder, value) => {
+            Operand::Value(value) => {
+                let placeholder = placeholders.push(value.clone());
                 let val = format!("{} {}", op, placeholder);
                 values.insert(placeholder, value);
                 val
             }
         };
 
-        match self.value {
-            SetValue::Attr(a) => {
-                let set_attr = a.into_attr_name();
-                let set_attr_name = format!("#{}", set_attr);
-                let expression = format!("{} = {} {}", attr_name, set_attr_name, op_expression);
-                names.insert(set_attr_name, set_attr);
-                SetOrRemove::Set(expression, names, values)
-            }
-            SetValue::Value(placeholder, value) => {
-                let expression = format!("{} = {} {}", attr_name, placeholder, op_expression);
-                values.insert(placeholder, value);
-                SetOrRemove::Set(expression, names, values)
-            }
-        }
+        let expression = format!("{} = {} {}", attr_name, value_expression, op_expression);
+        SetOrRemove::Set(expression, names, values)
     }
 }
 
@@ -213,7 +216,6 @@ mod tests {
 
     #[test]
     fn test_set_value_expression() {
-        crate::value_id::reset_value_id();
         if let SetOrRemove::Set(expression, names, values) =
             Set::new(UserAttrNames::Name).value("updated!!").build()
         {
@@ -231,7 +233,6 @@ mod tests {
 
     #[test]
     fn test_set_attr_expression_with_add_value() {
-        crate::value_id::reset_value_id();
         if let SetOrRemove::Set(expression, names, values) = Set::new(UserAttrNames::Age)
             .attr(UserAttrNames::Age)
             .add_value(10)
@@ -1,16 +1,75 @@
 use std::sync::atomic::AtomicUsize;
 static VALUE_ID: AtomicUsize = AtomicUsize::new(0);
 
+#[deprecated(note = "placeholders are allocated per request by `Placeholders`")]
 pub fn generate_value_id() -> usize {
     use std::sync::atomic::Ordering;
 
-    let id = VALUE_ID.load(Ordering::Relaxed);
-    VALUE_ID.store(id.wrapping_add(1), Ordering::Relaxed);
-    id
+    VALUE_ID.fetch_add(1, Ordering::Relaxed)
 }
 
+#[deprecated(note = "placeholders are allocated per request by `Placeholders`")]
 pub fn reset_value_id() {
     use std::sync::atomic::Ordering;
 
     VALUE_ID.store(0, Ordering::Relaxed);
 }
+
+// Allocates `:valueN` placeholders for a single request.
+// An identical value is registered only once, so the same placeholder is reused for it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Placeholders {
+    values: Vec<(super::Placeholder, super::AttributeValue)>,
+}
+
+impl Placeholders {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn push(&mut self, value: super::AttributeValue) -> super::Placeholder {
+        if let Some((placeholder, _)) = self.values.iter().find(|(_, v)| v == &value) {
+            return placeholder.clone();
+        }
+        let placeholder = format!(":value{}", self.values.len());
+        self.values.push((placeholder.clone(), value));
+        placeholder
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn into_attr_values(self) -> super::AttributeValues {
+        self.values.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoAttribute;
+
+    #[test]
+    fn test_placeholders_are_sequential() {
+        let mut placeholders = Placeholders::new();
+        assert_eq!(placeholders.push("bokuweb".into_attr()), ":value0");
+        assert_eq!(placeholders.push(1999.into_attr()), ":value1");
+        assert_eq!(placeholders.len(), 2);
+    }
+
+    #[test]
+    fn test_placeholders_dedup_identical_values() {
+        let mut placeholders = Placeholders::new();
+        assert_eq!(placeholders.push("20".into_attr()), ":value0");
+        assert_eq!(placeholders.push(20.into_attr()), ":value1");
+        assert_eq!(placeholders.push("20".into_attr()), ":value0");
+        let values = placeholders.into_attr_values();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values.get(":value0"), Some(&"20".into_attr()));
+    }
+}
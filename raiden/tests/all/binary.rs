@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::collections::HashSet;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Blob")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Blob {
+        #[raiden(partition_key)]
+        id: Binary,
+        #[raiden(sort_key)]
+        version: Binary,
+        chunks: HashSet<Binary>,
+    }
+
+    fn client() -> BlobClient {
+        Blob::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let attr = Binary::from(vec![0u8, 1, 255]).into_attr();
+        assert_eq!(attr.b, Some(bytes::Bytes::from(vec![0u8, 1, 255])));
+        assert_eq!(attr.l, None);
+        assert_eq!(
+            Binary::from_attr(Some(attr)).unwrap(),
+            Binary(vec![0, 1, 255])
+        );
+    }
+
+    #[test]
+    fn test_binary_set_round_trip() {
+        let set: HashSet<Binary> = vec![Binary(vec![1]), Binary(vec![2, 3])]
+            .into_iter()
+            .collect();
+        let attr = set.clone().into_attr();
+        assert_eq!(attr.bs.as_ref().map(|bs| bs.len()), Some(2));
+        assert_eq!(HashSet::<Binary>::from_attr(Some(attr)).unwrap(), set);
+        assert_eq!(
+            HashSet::<Binary>::new().into_attr(),
+            AttributeValue::default()
+        );
+    }
+
+    #[test]
+    fn test_binary_keys() {
+        let input = client().get(vec![1u8], Binary(vec![2])).input;
+        assert_eq!(input.key["id"].b, Some(bytes::Bytes::from(vec![1u8])));
+        assert_eq!(input.key["version"].b, Some(bytes::Bytes::from(vec![2u8])));
+
+        let (expression, _, values) = Blob::keys()
+            .id()
+            .eq(Binary(vec![1]))
+            .and(Blob::keys().version().begins_with(Binary(vec![2])))
+            .build();
+        assert_eq!(
+            expression,
+            "#id = :value0 AND (begins_with(#version, :value1))"
+        );
+        assert_eq!(values[":value1"].b, Some(bytes::Bytes::from(vec![2u8])));
+    }
+}
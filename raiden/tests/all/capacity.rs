@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[allow(dead_code)]
+    pub struct CapacityUser {
+        #[raiden(partition_key)]
+        id: String,
+    }
+
+    #[test]
+    fn test_builders_return_consumed_capacity() {
+        let client = CapacityUser::client(Region::UsEast1);
+        assert_eq!(client.get("id0").return_consumed_capacity, None);
+        assert_eq!(
+            client
+                .get("id0")
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .return_consumed_capacity,
+            Some(ReturnConsumedCapacity::Total)
+        );
+        assert_eq!(
+            client
+                .query()
+                .return_consumed_capacity(ReturnConsumedCapacity::Indexes)
+                .return_consumed_capacity,
+            Some(ReturnConsumedCapacity::Indexes)
+        );
+        assert_eq!(
+            client
+                .batch_delete(vec!["id0"])
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .return_consumed_capacity,
+            Some(ReturnConsumedCapacity::Total)
+        );
+    }
+
+    #[test]
+    fn test_client_shares_capacity_accumulator_with_builders() {
+        let accumulator = CapacityAccumulator::new();
+        let client =
+            CapacityUser::client(Region::UsEast1).with_capacity_accumulator(accumulator.clone());
+        let builder = client.scan();
+        builder
+            .capacity_accumulator
+            .unwrap()
+            .record(&Some(ConsumedCapacity {
+                table_name: Some("user".to_owned()),
+                capacity_units: Some(0.5),
+                ..ConsumedCapacity::default()
+            }));
+        assert_eq!(accumulator.total_capacity_units(), 0.5);
+        assert!(client.delete("id0").capacity_accumulator.is_some());
+    }
+}
@@ -80,7 +80,7 @@ mod tests {
         expected_names.insert("#id".to_owned(), "id".to_owned());
         let mut expected_values: raiden::AttributeValues = std::collections::HashMap::new();
         expected_values.insert(
-            ":typeS".to_owned(),
+            ":value0".to_owned(),
             raiden::AttributeValue {
                 s: Some("S".to_string()),
                 ..raiden::AttributeValue::default()
@@ -89,7 +89,7 @@ mod tests {
 
         assert_eq!(
             condition_expression,
-            "attribute_type(#id, :typeS)".to_owned()
+            "attribute_type(#id, :value0)".to_owned()
         );
         assert_eq!(attribute_names, expected_names);
         assert_eq!(attribute_values, expected_values);
@@ -103,7 +103,7 @@ mod tests {
         expected_names.insert("#name".to_owned(), "name".to_owned());
         let mut expected_values: raiden::AttributeValues = std::collections::HashMap::new();
         expected_values.insert(
-            ":begins_with_17d8e2e8233d9a6ae428061cb2cdf226".to_owned(),
+            ":value0".to_owned(),
             raiden::AttributeValue {
                 s: Some("boku".to_string()),
                 ..raiden::AttributeValue::default()
@@ -112,7 +112,7 @@ mod tests {
 
         assert_eq!(
             condition_expression,
-            "begins_with(#name, :begins_with_17d8e2e8233d9a6ae428061cb2cdf226)".to_owned()
+            "begins_with(#name, :value0)".to_owned()
         );
         assert_eq!(attribute_names, expected_names);
         assert_eq!(attribute_values, expected_values);
@@ -207,7 +207,6 @@ mod tests {
 
     #[test]
     fn test_cmp_eq_value_attr_condition() {
-        reset_value_id();
         let cond = User::condition().value("bokuweb").eq_attr(User::name());
         let (condition_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -225,4 +224,25 @@ mod tests {
         assert_eq!(attribute_names, expected_names);
         assert_eq!(attribute_values, expected_values);
     }
+
+    #[test]
+    fn test_typed_condition() {
+        let cond = User::cond()
+            .name()
+            .ne("bokuweb".to_owned())
+            .and(User::cond().id().not().exists());
+        let (condition_expression, attribute_names, attribute_values) = cond.build();
+        let mut expected_names: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        expected_names.insert("#name".to_owned(), "name".to_owned());
+        expected_names.insert("#id".to_owned(), "id".to_owned());
+        let mut expected_values: raiden::AttributeValues = std::collections::HashMap::new();
+        expected_values.insert(":value0".to_owned(), "bokuweb".into_attr());
+        assert_eq!(
+            condition_expression,
+            "#name <> :value0 AND (NOT (attribute_exists(#id)))".to_owned(),
+        );
+        assert_eq!(attribute_names, expected_names);
+        assert_eq!(attribute_values, expected_values);
+    }
 }
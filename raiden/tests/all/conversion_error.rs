@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::collections::HashMap;
+
+    fn list(items: Vec<AttributeValue>) -> AttributeValue {
+        AttributeValue {
+            l: Some(items),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let error = String::from_attr(Some(1.into_attr())).unwrap_err();
+        assert_eq!(error.kind(), &ConversionErrorKind::TypeMismatch);
+        assert_eq!(error.expected(), Some(&AttributeType::S));
+        assert_eq!(error.actual(), Some(&AttributeType::N));
+        assert_eq!(error.to_string(), "Type mismatch (expected S, found N)");
+    }
+
+    #[test]
+    fn test_nested_path() {
+        let mut inner = HashMap::new();
+        inner.insert(
+            "scores".to_owned(),
+            list(vec![1.into_attr(), "two".into_attr()]),
+        );
+        let value = AttributeValue {
+            m: Some(inner),
+            ..AttributeValue::default()
+        };
+        let error = HashMap::<String, Vec<usize>>::from_attr(Some(value))
+            .unwrap_err()
+            .at_key("stats");
+        assert_eq!(
+            error.path(),
+            &AttributePath(vec![
+                PathSegment::Key("stats".to_owned()),
+                PathSegment::Key("scores".to_owned()),
+                PathSegment::Index(1),
+            ])
+        );
+        assert_eq!(
+            error.to_string(),
+            "Type mismatch at `stats.scores[1]` (expected N, found S)"
+        );
+    }
+
+    #[test]
+    fn test_parse_cause() {
+        let error = u8::from_attr(Some(AttributeValue {
+            n: Some("256".to_owned()),
+            ..AttributeValue::default()
+        }))
+        .unwrap_err();
+        assert_eq!(error.kind(), &ConversionErrorKind::ParseInt);
+        let cause = std::error::Error::source(&error).unwrap();
+        assert_eq!(cause.to_string(), "number too large to fit in target type");
+    }
+
+    #[test]
+    fn test_raiden_error_source() {
+        let error = RaidenError::AttributeConvertError {
+            attr_name: "age".to_owned(),
+            source: ConversionError::missing(AttributeType::N).at_key("age"),
+        };
+        let source = std::error::Error::source(&error).unwrap();
+        assert_eq!(source.to_string(), "Value is none at `age` (expected N)");
+    }
+
+    #[test]
+    fn test_conversion_error_from_kind() {
+        let error: ConversionError = ConversionErrorKind::ValueIsNone.into();
+        assert_eq!(
+            error,
+            ConversionError::new(ConversionErrorKind::ValueIsNone)
+        );
+        assert!(usize::from_attr(None).unwrap_err() == ConversionErrorKind::ValueIsNone);
+    }
+}
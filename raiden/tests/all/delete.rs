@@ -85,4 +85,19 @@ mod tests {
         }
         rt.block_on(example());
     }
+
+    #[tokio::test]
+    async fn test_delete_item_returns_consumed_capacity() {
+        let client = DeleteTest0::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        });
+        let res = client
+            .delete("unstored")
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .run()
+            .await
+            .unwrap();
+        assert!(res.consumed_capacity.unwrap().capacity_units.unwrap() > 0.0);
+    }
 }
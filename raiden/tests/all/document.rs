@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Document")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Document {
+        #[raiden(partition_key)]
+        id: String,
+        labels: HashMap<String, String>,
+        counts: BTreeMap<String, usize>,
+        body: serde_json::Value,
+    }
+
+    #[test]
+    fn test_map_round_trip() {
+        let mut labels = HashMap::new();
+        labels.insert("env".to_owned(), "prod".to_owned());
+        let attr = labels.clone().into_attr();
+        assert_eq!(attr.m.as_ref().unwrap()["env"].s, Some("prod".to_owned()));
+        assert_eq!(
+            HashMap::<String, String>::from_attr(Some(attr)).unwrap(),
+            labels
+        );
+
+        let mut counts = BTreeMap::new();
+        counts.insert("a".to_owned(), 1usize);
+        counts.insert("b".to_owned(), 2usize);
+        let attr = counts.clone().into_attr();
+        assert_eq!(
+            BTreeMap::<String, usize>::from_attr(Some(attr)).unwrap(),
+            counts
+        );
+        assert_eq!(
+            BTreeMap::<String, usize>::from_attr(None).unwrap(),
+            BTreeMap::new()
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = serde_json::json!({
+            "name": "bokuweb",
+            "age": 36,
+            "ratio": 0.5,
+            "admin": false,
+            "nickname": null,
+            "tags": ["a", 1, [true]],
+            "nested": { "empty": {} },
+        });
+        let attr = value.clone().into_attr();
+        let m = attr.m.as_ref().unwrap();
+        assert_eq!(m["age"].n, Some("36".to_owned()));
+        assert_eq!(m["admin"].bool, Some(false));
+        assert_eq!(m["nickname"].null, Some(true));
+        assert_eq!(m["tags"].l.as_ref().map(|l| l.len()), Some(3));
+        assert_eq!(serde_json::Value::from_attr(Some(attr)).unwrap(), value);
+    }
+
+    #[test]
+    fn test_json_from_sets() {
+        let attr = AttributeValue {
+            ns: Some(vec!["1".to_owned(), "2.5".to_owned()]),
+            ..AttributeValue::default()
+        };
+        assert_eq!(
+            serde_json::Value::from_attr(Some(attr)).unwrap(),
+            serde_json::json!([1, 2.5])
+        );
+    }
+}
@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::collections::{HashMap, HashSet};
+
+    fn null() -> AttributeValue {
+        AttributeValue {
+            null: Some(true),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_policy() {
+        let empty = "".into_attr();
+        assert_eq!(EmptyValuePolicy::Omit.apply(empty.clone()), None);
+        assert_eq!(EmptyValuePolicy::Null.apply(empty.clone()), Some(null()));
+        assert_eq!(EmptyValuePolicy::Empty.apply(empty.clone()), Some(empty));
+
+        let set = HashSet::<String>::new().into_attr();
+        assert_eq!(EmptyValuePolicy::Null.apply(set.clone()), Some(null()));
+        assert_eq!(EmptyValuePolicy::Empty.apply(set), None);
+
+        let name = "bokuweb".into_attr();
+        assert_eq!(EmptyValuePolicy::Omit.apply(name.clone()), Some(name));
+    }
+
+    #[test]
+    fn test_empty_values_are_consistent() {
+        assert_eq!("".into_attr(), String::new().into_attr());
+        assert_eq!(
+            Vec::<String>::new().into_attr(),
+            AttributeValue {
+                l: Some(vec![]),
+                ..AttributeValue::default()
+            }
+        );
+        assert!(is_empty_value(&Vec::<usize>::new().into_attr()));
+        assert!(is_empty_value(&HashMap::<String, usize>::new().into_attr()));
+        assert!(is_empty_value(&Binary::default().into_attr()));
+        assert!(!is_empty_value(&null()));
+        assert!(!is_empty_value(&0.into_attr()));
+    }
+
+    #[test]
+    fn test_read_omitted_attribute() {
+        let value = EmptyValuePolicy::Omit.read(None);
+        assert_eq!(String::from_attr(value.clone()), Ok("".to_owned()));
+        assert_eq!(Vec::<String>::from_attr(value), Ok(vec![]));
+        assert_eq!(EmptyValuePolicy::Null.read(None), None);
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "EmptyValue")]
+    #[raiden(empty_value = "omit")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct EmptyValue {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        #[raiden(empty_value = "null")]
+        tags: Vec<String>,
+        #[raiden(empty_value = "empty")]
+        note: String,
+    }
+
+    fn client() -> EmptyValueClient {
+        EmptyValue::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[test]
+    fn test_put_with_policy() {
+        let item = EmptyValuePutItemInput {
+            id: "id0".to_owned(),
+            name: "".to_owned(),
+            tags: vec![],
+            note: "".to_owned(),
+        };
+        let item = client().put(item).input.item;
+        assert_eq!(item.get("name"), None);
+        assert_eq!(item["tags"], null());
+        assert_eq!(item["note"], "".into_attr());
+    }
+
+    #[test]
+    fn test_transact_put_with_policy() {
+        let item = EmptyValuePutItemInput {
+            id: "id0".to_owned(),
+            name: "".to_owned(),
+            tags: vec![],
+            note: "".to_owned(),
+        };
+        let item = EmptyValue::put(item).input.item;
+        assert_eq!(item.get("name"), None);
+        assert_eq!(item["tags"], null());
+    }
+
+    #[test]
+    fn test_update_set_with_policy() {
+        let set_name = EmptyValue::update_expression()
+            .set(EmptyValue::name())
+            .value("");
+        let set_tags = EmptyValue::update_expression()
+            .set(EmptyValue::tags())
+            .value(Vec::<String>::new());
+        let client = client();
+        let builder = client.update("id0").set(set_name).set(set_tags);
+        match &builder.set_items[0] {
+            update_expression::SetOrRemove::Remove(expression, _) => {
+                assert_eq!(expression, "#name")
+            }
+            _ => panic!("should be removed"),
+        }
+        match &builder.set_items[1] {
+            update_expression::SetOrRemove::Set(expression, _, values) => {
+                assert_eq!(expression, "#tags = :value0");
+                assert_eq!(values[":value0"], null());
+            }
+            _ => panic!("should be set"),
+        }
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "EmptyValue")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct DefaultEmptyValue {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    fn test_null_is_default() {
+        assert_eq!(EmptyValuePolicy::default(), EmptyValuePolicy::Null);
+        let client = DefaultEmptyValue::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        });
+        let item = DefaultEmptyValuePutItemInput {
+            id: "id0".to_owned(),
+            name: "".to_owned(),
+            tags: vec![],
+        };
+        let item = client.put(item).input.item;
+        assert_eq!(item["name"], null());
+        assert_eq!(item["tags"], null());
+
+        let set_name = DefaultEmptyValue::update_expression()
+            .set(DefaultEmptyValue::name())
+            .value("");
+        let builder = client.update("id0").set(set_name);
+        match &builder.set_items[0] {
+            update_expression::SetOrRemove::Set(expression, _, values) => {
+                assert_eq!(expression, "#name = :value0");
+                assert_eq!(values[":value0"], null());
+            }
+            _ => panic!("should be set"),
+        }
+    }
+}
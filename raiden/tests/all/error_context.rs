@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    fn context() -> ErrorContext {
+        let mut key = std::collections::HashMap::new();
+        key.insert("id".to_owned(), "id0".into_attr());
+        ErrorContext::new(Operation::PutItem)
+            .with_table_name("dev_user")
+            .with_key(key)
+            .with_attempts(2)
+    }
+
+    #[test]
+    fn test_error_with_context() {
+        let error =
+            RaidenError::ConditionalCheckFailed("The conditional request failed".to_owned())
+                .with_context(context());
+        assert_eq!(error.context, context());
+        assert!(matches!(
+            error.error,
+            RaidenError::ConditionalCheckFailed(_)
+        ));
+        assert_eq!(
+            error.to_string(),
+            "`The conditional request failed` (PutItem on `dev_user` with key {\"id\":\"id0\"}, attempt 2)"
+        );
+    }
+
+    #[test]
+    fn test_error_derefs_to_source() {
+        let error = RaidenError::ThrottlingException("".to_owned())
+            .with_context(context().with_request_id(Some("request0".to_owned())));
+        assert!(error.is_throttling());
+        assert!(DefaultRetryStrategy.should_retry(&error));
+        assert!(error.to_string().ends_with(", request id request0)"));
+        assert_eq!(
+            RaidenError::from(error),
+            RaidenError::ThrottlingException("".to_owned())
+        );
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::expr::*;
+    use raiden::update_expression::{ArithmeticOperator, SetAction, UpdateExpr};
+    use raiden::*;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[derive(Debug, Clone)]
+    pub struct User {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        year: usize,
+        num: usize,
+    }
+
+    fn names(pairs: &[(&str, &str)]) -> AttributeNames {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        let mut values = AttributeValues::new();
+        values.insert(":v".to_owned(), 30.into_attr());
+        let expr = parse_condition(
+            "attribute_exists(#a) AND #b > :v",
+            &names(&[("#a", "name"), ("#b", "year")]),
+            &values,
+        )
+        .unwrap();
+        let expected = ConditionExpr {
+            not: false,
+            cond: Cond::Func(ConditionFunctionExpression::AttributeExists(
+                "name".to_owned(),
+            )),
+            operator: Some(Operator::And(Box::new(ConditionExpr {
+                not: false,
+                cond: Cond::Cmp(ConditionComparisonExpression::Gt(
+                    AttrOrValue::Attr("year".to_owned()),
+                    AttrOrValue::Value(30.into_attr()),
+                )),
+                operator: None,
+            }))),
+        };
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn test_parse_condition_round_trip() {
+        let expr = User::condition()
+            .not()
+            .attr_exists(User::name())
+            .and(
+                User::condition()
+                    .value("bokuweb")
+                    .eq_attr(User::name())
+                    .or(User::condition().attr_type(User::year(), AttributeType::N)),
+            )
+            .into_expr();
+        let (expression, names, values) = expr.render(&mut Placeholders::new());
+        assert_eq!(parse_condition(&expression, &names, &values), Ok(expr));
+    }
+
+    #[test]
+    fn test_parse_condition_groups_mixed_operators() {
+        let mut values = AttributeValues::new();
+        values.insert(":a".to_owned(), 1.into_attr());
+        values.insert(":b".to_owned(), 2.into_attr());
+        let expr = parse_condition(
+            "#a = :a AND #b BETWEEN :a AND :b OR NOT (attribute_exists(#a) OR size = :b)",
+            &names(&[("#a", "year"), ("#b", "num")]),
+            &values,
+        )
+        .unwrap();
+        assert!(matches!(expr.cond, Cond::Group(_)));
+        assert!(matches!(expr.operator, Some(Operator::Or(_))));
+
+        let (expression, names, values) = expr.render(&mut Placeholders::new());
+        assert_eq!(
+            expression,
+            "(#year = :value0 AND (#num BETWEEN :value0 AND :value1)) OR (NOT ((attribute_exists(#year) OR (#size = :value1))))"
+        );
+        assert_eq!(parse_condition(&expression, &names, &values), Ok(expr));
+    }
+
+    #[test]
+    fn test_parsed_condition_is_typed_per_table() {
+        let mut values = AttributeValues::new();
+        values.insert(":v".to_owned(), 30.into_attr());
+        let expr = parse_condition("#a > :v", &names(&[("#a", "year")]), &values).unwrap();
+        let cond = User::condition()
+            .attr_exists(User::name())
+            .and(User::condition_from(expr));
+        let (expression, names, _) = cond.build();
+        assert_eq!(expression, "attribute_exists(#name) AND (#year > :value0)");
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_filter_round_trip() {
+        let expr = User::filter_expression(User::name())
+            .size()
+            .gt(3)
+            .and(
+                User::filter_expression(User::year())
+                    .between(1999, 2020)
+                    .or(User::filter_expression(User::id()).begins_with("id")),
+            )
+            .into_expr();
+        let (expression, names, values) = expr.render(&mut Placeholders::new());
+        assert_eq!(
+            expression,
+            "size(#name) > :value0 AND (#year BETWEEN :value1 AND :value2 OR (begins_with(#id, :value3)))"
+        );
+        assert_eq!(parse_filter(&expression, &names, &values), Ok(expr));
+    }
+
+    #[test]
+    fn test_parse_filter_rejects_not() {
+        let result = parse_filter(
+            "NOT attribute_exists(#a)",
+            &names(&[("#a", "name")]),
+            &AttributeValues::new(),
+        );
+        assert!(matches!(result, Err(ExprError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_parse_key_condition_round_trip() {
+        let expr = User::key_condition(User::id())
+            .eq("id0")
+            .and(User::key_condition(User::name()).begins_with("boku"))
+            .into_expr();
+        let (expression, names, values) = expr.render(&mut Placeholders::new());
+        assert_eq!(parse_key_condition(&expression, &names, &values), Ok(expr));
+    }
+
+    #[test]
+    fn test_parse_key_condition_rejects_or() {
+        let mut values = AttributeValues::new();
+        values.insert(":v".to_owned(), "id0".into_attr());
+        let result = parse_key_condition("#id = :v OR #id = :v", &names(&[("#id", "id")]), &values);
+        assert_eq!(
+            result,
+            Err(ExprError::Unsupported("OR in key conditions".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_update() {
+        let mut values = AttributeValues::new();
+        values.insert(":name".to_owned(), "bokuweb".into_attr());
+        values.insert(":one".to_owned(), 1.into_attr());
+        let expr = parse_update(
+            "set #name = :name, #num = #num + :one remove #year ADD #id :one",
+            &names(&[
+                ("#name", "name"),
+                ("#num", "num"),
+                ("#year", "year"),
+                ("#id", "id"),
+            ]),
+            &values,
+        )
+        .unwrap();
+        let expected = UpdateExpr {
+            add: vec![("id".to_owned(), 1.into_attr())],
+            set: vec![
+                SetAction {
+                    attr: "name".to_owned(),
+                    value: AttrOrValue::Value("bokuweb".into_attr()),
+                    operation: None,
+                },
+                SetAction {
+                    attr: "num".to_owned(),
+                    value: AttrOrValue::Attr("num".to_owned()),
+                    operation: Some((ArithmeticOperator::Add, AttrOrValue::Value(1.into_attr()))),
+                },
+            ],
+            remove: vec!["year".to_owned()],
+            delete: vec![],
+        };
+        assert_eq!(expr, expected);
+
+        let (expression, names, values) = expr.render(&mut Placeholders::new());
+        assert_eq!(
+            expression,
+            "ADD #id :value0 SET #name = :value1, #num = #num + :value0 REMOVE #year"
+        );
+        assert_eq!(parse_update(&expression, &names, &values), Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_update_rejects_duplicate_clause() {
+        let result = parse_update(
+            "REMOVE #a REMOVE #a",
+            &names(&[("#a", "name")]),
+            &AttributeValues::new(),
+        );
+        assert_eq!(result, Err(ExprError::DuplicateClause("REMOVE".to_owned())));
+    }
+
+    #[test]
+    fn test_parse_projection() {
+        assert_eq!(
+            parse_projection("#id, name", &names(&[("#id", "id")])),
+            Ok(vec!["id".to_owned(), "name".to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let values = AttributeValues::new();
+        assert_eq!(
+            parse_condition("attribute_exists(#a)", &AttributeNames::new(), &values),
+            Err(ExprError::UnknownName("#a".to_owned()))
+        );
+        assert_eq!(
+            parse_condition("name = :v", &AttributeNames::new(), &values),
+            Err(ExprError::UnknownValue(":v".to_owned()))
+        );
+        assert_eq!(
+            parse_condition(
+                "attribute_exists(name) name",
+                &AttributeNames::new(),
+                &values
+            ),
+            Err(ExprError::UnexpectedToken("name".to_owned()))
+        );
+        assert_eq!(
+            parse_condition("attribute_exists(name", &AttributeNames::new(), &values),
+            Err(ExprError::UnexpectedEnd)
+        );
+    }
+}
@@ -20,7 +20,6 @@ mod tests {
 
     #[test]
     fn test_eq_filter_expression() {
-        reset_value_id();
         let cond = User::filter_expression(User::name()).eq("bokuweb");
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -36,7 +35,6 @@ mod tests {
 
     #[test]
     fn test_size_filter_expression() {
-        reset_value_id();
         let cond = User::filter_expression(User::name()).size().eq(7);
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -52,7 +50,6 @@ mod tests {
 
     #[test]
     fn test_not_filter_expression() {
-        reset_value_id();
         let cond = User::filter_expression(User::name()).not("raiden");
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -69,8 +66,6 @@ mod tests {
 
     #[test]
     fn test_two_and_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).eq("bokuweb").and(
             User::filter_expression(User::year())
                 .eq(1999)
@@ -99,8 +94,6 @@ mod tests {
 
     #[test]
     fn test_two_or_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name())
             .eq("bokuweb")
             .or(User::filter_expression(User::year())
@@ -129,8 +122,6 @@ mod tests {
 
     #[test]
     fn test_begins_with_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).begins_with("bokuweb");
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -146,8 +137,6 @@ mod tests {
 
     #[test]
     fn test_begins_with_id_and_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::id())
             .not("id3")
             .and(User::filter_expression(User::year()).begins_with("20"));
@@ -160,8 +149,6 @@ mod tests {
 
     #[test]
     fn test_attribute_exists_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).attribute_exists();
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -176,8 +163,6 @@ mod tests {
 
     #[test]
     fn test_attribute_not_exists_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).attribute_not_exists();
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -192,8 +177,6 @@ mod tests {
 
     #[test]
     fn test_attribute_type_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).attribute_type(raiden::AttributeType::S);
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -212,8 +195,6 @@ mod tests {
 
     #[test]
     fn test_contains_filter_expression() {
-        reset_value_id();
-
         let cond = User::filter_expression(User::name()).contains("boku");
         let (filter_expression, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -226,4 +207,51 @@ mod tests {
         assert_eq!(attribute_names, expected_names);
         assert_eq!(attribute_values, expected_values);
     }
+
+    #[test]
+    fn test_filter_expressions_share_placeholders() {
+        let mut placeholders = Placeholders::new();
+        let (first, _, first_values) = User::filter_expression(User::name())
+            .eq("bokuweb")
+            .build_with(&mut placeholders);
+        let (second, _, second_values) = User::filter_expression(User::year())
+            .eq(1999)
+            .and(User::filter_expression(User::name()).eq("bokuweb"))
+            .build_with(&mut placeholders);
+        assert_eq!(first, "#name = :value0".to_owned());
+        assert_eq!(second, "#year = :value1 AND (#name = :value0)".to_owned());
+        assert_eq!(first_values.len(), 1);
+        assert_eq!(second_values.len(), 2);
+        assert_eq!(placeholders.len(), 2);
+    }
+
+    #[test]
+    fn test_typed_filter_expression() {
+        let cond = User::filter().year().gt(1999).and(
+            User::filter()
+                .name()
+                .size()
+                .le(10)
+                .or(User::filter().renamed().between(1, 3)),
+        );
+        let (filter_expression, attribute_names, attribute_values) = cond.build();
+        let mut expected_names: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        expected_names.insert("#year".to_owned(), "year".to_owned());
+        expected_names.insert("#name".to_owned(), "name".to_owned());
+        expected_names.insert("#Renamed".to_owned(), "Renamed".to_owned());
+        let mut expected_values: std::collections::HashMap<String, AttributeValue> =
+            std::collections::HashMap::new();
+        expected_values.insert(":value0".to_owned(), 1999.into_attr());
+        expected_values.insert(":value1".to_owned(), 10.into_attr());
+        expected_values.insert(":value2".to_owned(), 1.into_attr());
+        expected_values.insert(":value3".to_owned(), 3.into_attr());
+        assert_eq!(
+            filter_expression,
+            "#year > :value0 AND (size(#name) <= :value1 OR (#Renamed BETWEEN :value2 AND :value3))"
+                .to_owned(),
+        );
+        assert_eq!(attribute_names, expected_names);
+        assert_eq!(attribute_values, expected_values);
+    }
 }
@@ -0,0 +1,166 @@
+#[cfg(all(test, feature = "chrono"))]
+mod chrono_tests {
+
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::format::{AttributeFormat, EpochMillis, Rfc3339};
+    use raiden::*;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Event")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Event {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(sort_key)]
+        #[raiden(format = "epoch_millis")]
+        occurred_at: DateTime<Utc>,
+        created_at: DateTime<Utc>,
+        #[raiden(format = "epoch_millis")]
+        day: Option<NaiveDate>,
+    }
+
+    fn client() -> EventClient {
+        Event::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[test]
+    fn test_rfc3339_is_fixed_width() {
+        let whole = Utc.timestamp_millis_opt(1_600_000_000_000).unwrap();
+        let fractional = Utc.timestamp_millis_opt(1_600_000_000_500).unwrap();
+        let whole_attr = whole.into_attr();
+        let fractional_attr = fractional.into_attr();
+        assert_eq!(
+            whole_attr.s,
+            Some("2020-09-13T12:26:40.000000000Z".to_owned())
+        );
+        assert!(whole_attr.s < fractional_attr.s);
+        assert_eq!(DateTime::<Utc>::from_attr(Some(whole_attr)).unwrap(), whole);
+    }
+
+    #[test]
+    fn test_epoch_millis_round_trip() {
+        let datetime = Utc.timestamp_millis_opt(1_600_000_000_123).unwrap();
+        let attr = EpochMillis::into_attr(datetime).unwrap();
+        assert_eq!(attr.n, Some("1600000000123".to_owned()));
+        let parsed: DateTime<Utc> = EpochMillis::from_attr(Some(attr)).unwrap();
+        assert_eq!(parsed, datetime);
+
+        let date = NaiveDate::from_ymd_opt(2020, 9, 13).unwrap();
+        let attr = EpochMillis::into_attr(date).unwrap();
+        assert_eq!(attr.n, Some("1599955200000".to_owned()));
+        let parsed: NaiveDate = EpochMillis::from_attr(Some(attr)).unwrap();
+        assert_eq!(parsed, date);
+    }
+
+    #[test]
+    fn test_epoch_millis_floors_before_1970() {
+        let datetime = Utc.timestamp_nanos(-1_500_000);
+        let attr = EpochMillis::into_attr(datetime).unwrap();
+        assert_eq!(attr.n, Some("-2".to_owned()));
+    }
+
+    #[test]
+    fn test_format_parse_error() {
+        let attr = AttributeValue {
+            s: Some("not a date".to_owned()),
+            ..AttributeValue::default()
+        };
+        let parsed: Result<NaiveDate, ConversionError> = Rfc3339::from_attr(Some(attr));
+        assert_eq!(
+            parsed.unwrap_err().kind(),
+            &ConversionErrorKind::ParseDateTime
+        );
+    }
+
+    #[test]
+    fn test_put_item_with_format() {
+        let occurred_at = Utc.timestamp_millis_opt(1_600_000_000_000).unwrap();
+        let event = EventPutItemInput {
+            id: "id0".to_owned(),
+            occurred_at,
+            created_at: occurred_at,
+            day: None,
+        };
+        let item = client().put(event).input.item;
+        assert_eq!(item["occurred_at"].n, Some("1600000000000".to_owned()));
+        assert_eq!(
+            item["created_at"].s,
+            Some("2020-09-13T12:26:40.000000000Z".to_owned())
+        );
+        assert_eq!(item["day"].null, Some(true));
+    }
+
+    #[test]
+    fn test_get_key_with_format() {
+        let occurred_at = Utc.timestamp_millis_opt(1_600_000_000_000).unwrap();
+        let key = client().get("id0", occurred_at).input.key;
+        assert_eq!(key["occurred_at"].n, Some("1600000000000".to_owned()));
+    }
+
+    #[test]
+    fn test_accessors_with_format() {
+        let occurred_at = Utc.timestamp_millis_opt(1_600_000_000_000).unwrap();
+        let (expression, _, values) = Event::keys()
+            .id()
+            .eq("id0".to_owned())
+            .and(Event::keys().occurred_at().ge(occurred_at))
+            .build();
+        assert_eq!(
+            expression,
+            "#id = :value0 AND (#occurred_at >= :value1)".to_owned()
+        );
+        assert_eq!(values[":value1"].n, Some("1600000000000".to_owned()));
+
+        let day = NaiveDate::from_ymd_opt(2020, 9, 13).unwrap();
+        let (_, _, values) = Event::filter().day().eq(day).build();
+        assert_eq!(values[":value0"].n, Some("1599955200000".to_owned()));
+        let (_, _, values) = Event::cond().occurred_at().lt(occurred_at).build();
+        assert_eq!(values[":value0"].n, Some("1600000000000".to_owned()));
+    }
+}
+
+#[cfg(all(test, feature = "time"))]
+mod time_tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::format::{AttributeFormat, EpochMillis};
+    use raiden::*;
+    use time::OffsetDateTime;
+
+    #[test]
+    fn test_offset_date_time_round_trip() {
+        let datetime = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+        let attr = datetime.into_attr();
+        assert_eq!(attr.s, Some("2020-09-13T12:26:40.000000000Z".to_owned()));
+        assert_eq!(OffsetDateTime::from_attr(Some(attr)).unwrap(), datetime);
+
+        let attr = EpochMillis::into_attr(datetime).unwrap();
+        assert_eq!(attr.n, Some("1600000000000".to_owned()));
+        let parsed: OffsetDateTime = EpochMillis::from_attr(Some(attr)).unwrap();
+        assert_eq!(parsed, datetime);
+    }
+
+    #[test]
+    fn test_epoch_millis_floors_before_1970() {
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(-1_500_000).unwrap();
+        let attr = EpochMillis::into_attr(datetime).unwrap();
+        assert_eq!(attr.n, Some("-2".to_owned()));
+        let parsed: OffsetDateTime = EpochMillis::from_attr(Some(attr)).unwrap();
+        assert_eq!(parsed.unix_timestamp_nanos(), -2_000_000);
+    }
+
+    #[test]
+    fn test_rfc3339_before_1970() {
+        let datetime = OffsetDateTime::from_unix_timestamp_nanos(-1_500_000).unwrap();
+        let attr = datetime.into_attr();
+        assert_eq!(attr.s, Some("1969-12-31T23:59:59.998500000Z".to_owned()));
+        assert_eq!(OffsetDateTime::from_attr(Some(attr)).unwrap(), datetime);
+    }
+}
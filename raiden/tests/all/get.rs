@@ -95,6 +95,32 @@ mod tests {
         rt.block_on(example());
     }
 
+    #[tokio::test]
+    async fn test_user_get_item_with_context() {
+        let client = UserClient::new(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        });
+        let error = client
+            .get("not_exist_key")
+            .consistent()
+            .run_with_context()
+            .await
+            .unwrap_err();
+        assert_eq!(error.context.operation, Operation::GetItem);
+        assert_eq!(error.context.table_name, Some("user".to_owned()));
+        assert_eq!(
+            error.context.key.as_ref().unwrap()["id"],
+            "not_exist_key".into_attr()
+        );
+        assert_eq!(error.context.attempts, 1);
+        assert!(error.context.request_id.is_some());
+        assert!(matches!(
+            RaidenError::from(error),
+            RaidenError::ResourceNotFound(_)
+        ));
+    }
+
     #[derive(Raiden)]
     #[raiden(table_name = "user")]
     #[derive(Debug, Clone, PartialEq)]
@@ -121,6 +147,7 @@ mod tests {
                 // }),
                 Err(RaidenError::AttributeConvertError {
                     attr_name: "unstored".to_owned(),
+                    source: ConversionError::missing(AttributeType::N).at_key("unstored"),
                 }),
             );
         }
@@ -563,6 +590,69 @@ mod tests {
         rt.block_on(example());
     }
 
+    fn anonymous() -> String {
+        "anonymous".to_owned()
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "DefaultFnTestData0")]
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DefaultFn {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(default = "anonymous")]
+        name: String,
+        #[raiden(default_on_error)]
+        count: usize,
+        #[raiden(default = "anonymous")]
+        #[raiden(default_on_error)]
+        label: String,
+    }
+
+    #[test]
+    fn test_default_fn_for_missing_attributes() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        async fn example() {
+            let client = DefaultFn::client(Region::Custom {
+                endpoint: "http://localhost:8000".into(),
+                name: "ap-northeast-1".into(),
+            });
+            let res = client.get("id0").run().await;
+            assert_eq!(
+                res.unwrap().item,
+                DefaultFn {
+                    id: "id0".to_owned(),
+                    name: "anonymous".to_owned(),
+                    count: 0,
+                    label: "anonymous".to_owned(),
+                }
+            );
+        }
+        rt.block_on(example());
+    }
+
+    #[test]
+    fn test_default_on_conversion_error() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        async fn example() {
+            let client = DefaultFn::client(Region::Custom {
+                endpoint: "http://localhost:8000".into(),
+                name: "ap-northeast-1".into(),
+            });
+            let res = client.get("id1").run().await;
+            assert_eq!(
+                res.unwrap().item,
+                DefaultFn {
+                    id: "id1".to_owned(),
+                    name: "bokuweb".to_owned(),
+                    count: 0,
+                    label: "anonymous".to_owned(),
+                }
+            );
+        }
+        rt.block_on(example());
+    }
+
     #[derive(Raiden)]
     #[raiden(table_name = "FloatTest")]
     #[derive(Debug, Clone, PartialEq)]
@@ -596,4 +686,48 @@ mod tests {
         }
         rt.block_on(example());
     }
+
+    type Recorded = (Operation, Option<String>, Outcome, usize);
+
+    #[derive(Clone, Default)]
+    struct RecordedMetrics(std::sync::Arc<std::sync::Mutex<Vec<Recorded>>>);
+
+    impl RaidenMetrics for RecordedMetrics {
+        fn record(&self, metrics: &OperationMetrics<'_>) {
+            self.0.lock().unwrap().push((
+                metrics.operation,
+                metrics.table_name.map(str::to_owned),
+                metrics.outcome,
+                metrics.item_count,
+            ));
+        }
+    }
+
+    #[test]
+    fn test_get_item_reports_metrics() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        async fn example() {
+            let metrics = RecordedMetrics::default();
+            let client = User::client(Region::Custom {
+                endpoint: "http://localhost:8000".into(),
+                name: "ap-northeast-1".into(),
+            })
+            .with_metrics(metrics.clone());
+            client.get("user_primary_key").run().await.unwrap();
+            assert!(client.get("unknown_key").run().await.is_err());
+            assert_eq!(
+                *metrics.0.lock().unwrap(),
+                vec![
+                    (Operation::GetItem, Some("user".to_owned()), Outcome::Ok, 1),
+                    (
+                        Operation::GetItem,
+                        Some("user".to_owned()),
+                        Outcome::Other,
+                        0
+                    ),
+                ]
+            );
+        }
+        rt.block_on(example());
+    }
 }
@@ -20,7 +20,6 @@ mod tests {
 
     #[test]
     fn test_eq_key_condition() {
-        reset_value_id();
         let cond = User::key_condition(User::name()).eq("bokuweb");
         let (key_condition, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -36,8 +35,6 @@ mod tests {
 
     #[test]
     fn test_two_and_key_condition() {
-        reset_value_id();
-
         let cond = User::key_condition(User::name()).eq("bokuweb").and(
             User::key_condition(User::year())
                 .eq(1999)
@@ -66,8 +63,6 @@ mod tests {
 
     #[test]
     fn test_begins_with_key_condition() {
-        reset_value_id();
-
         let cond = User::key_condition(User::name()).begins_with("bokuweb");
         let (key_condition, attribute_names, attribute_values) = cond.build();
         let mut expected_names: std::collections::HashMap<String, String> =
@@ -83,8 +78,6 @@ mod tests {
 
     #[test]
     fn test_begins_with_id_and_key_condition() {
-        reset_value_id();
-
         let cond = User::key_condition(User::id())
             .eq("id3")
             .and(User::key_condition(User::year()).begins_with("20"));
@@ -94,4 +87,54 @@ mod tests {
             "#id = :value0 AND (begins_with(#year, :value1))".to_owned(),
         );
     }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "post")]
+    #[derive(Debug, Clone)]
+    pub struct Post {
+        #[raiden(partition_key)]
+        user_id: String,
+        #[raiden(sort_key)]
+        created_at: usize,
+        title: String,
+    }
+
+    #[test]
+    fn test_partition_key_condition() {
+        let cond = User::keys().id().eq("id0".to_owned());
+        let (key_condition, attribute_names, attribute_values) = cond.build();
+        let mut expected_names: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        expected_names.insert("#id".to_owned(), "id".to_owned());
+        let mut expected_values: std::collections::HashMap<String, AttributeValue> =
+            std::collections::HashMap::new();
+        expected_values.insert(":value0".to_owned(), "id0".into_attr());
+        assert_eq!(key_condition, "#id = :value0".to_owned());
+        assert_eq!(attribute_names, expected_names);
+        assert_eq!(attribute_values, expected_values);
+    }
+
+    #[test]
+    fn test_partition_and_sort_key_condition() {
+        let cond = Post::keys()
+            .user_id()
+            .eq("user0".to_owned())
+            .and(Post::keys().created_at().between(1999, 2020));
+        let (key_condition, attribute_names, attribute_values) = cond.build();
+        let mut expected_names: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        expected_names.insert("#user_id".to_owned(), "user_id".to_owned());
+        expected_names.insert("#created_at".to_owned(), "created_at".to_owned());
+        let mut expected_values: std::collections::HashMap<String, AttributeValue> =
+            std::collections::HashMap::new();
+        expected_values.insert(":value0".to_owned(), "user0".into_attr());
+        expected_values.insert(":value1".to_owned(), 1999.into_attr());
+        expected_values.insert(":value2".to_owned(), 2020.into_attr());
+        assert_eq!(
+            key_condition,
+            "#user_id = :value0 AND (#created_at BETWEEN :value1 AND :value2)".to_owned(),
+        );
+        assert_eq!(attribute_names, expected_names);
+        assert_eq!(attribute_values, expected_values);
+    }
 }
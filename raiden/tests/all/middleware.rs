@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MiddlewareUser {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+    }
+
+    // Answers every `GetItem` itself, so nothing reaches DynamoDB.
+    struct Stub;
+
+    #[async_trait]
+    impl Layer for Stub {
+        async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+            match request {
+                Request::GetItem(input) => {
+                    let mut item = input.key;
+                    item.insert(
+                        "name".to_owned(),
+                        AttributeValue {
+                            s: Some(input.table_name),
+                            ..AttributeValue::default()
+                        },
+                    );
+                    Ok(Response::GetItem(GetItemOutput {
+                        item: Some(item),
+                        ..GetItemOutput::default()
+                    }))
+                }
+                request => next.run(request).await,
+            }
+        }
+    }
+
+    // Only lets requests to tables of the tenant through.
+    struct Tenant(&'static str);
+
+    #[async_trait]
+    impl Layer for Tenant {
+        async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+            if request
+                .table_names()
+                .iter()
+                .any(|table_name| !table_name.starts_with(self.0))
+            {
+                return Err(RaidenError::AccessDenied(format!(
+                    "{} is out of the tenant",
+                    request.table_names().join(",")
+                )));
+            }
+            next.run(request).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layer_short_circuits_get_item() {
+        let client = MiddlewareUser::client(Region::UsEast1)
+            .table_prefix("tenant_")
+            .layer(Tenant("tenant_"))
+            .layer(Stub);
+        let res = client.get("id0").run().await.unwrap();
+        assert_eq!(
+            res.item,
+            MiddlewareUser {
+                id: "id0".to_owned(),
+                name: "tenant_user".to_owned(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_layer_rejects_request() {
+        let client = MiddlewareUser::client(Region::UsEast1)
+            .layer(Tenant("tenant_"))
+            .layer(Stub);
+        let res = client.get("id0").run().await;
+        assert!(matches!(
+            res.unwrap_err(),
+            RaidenError::AccessDenied(message) if message == "user is out of the tenant"
+        ));
+
+        let res = WriteTx::new(Region::UsEast1)
+            .layer(Tenant("tenant_"))
+            .put(MiddlewareUser::put(
+                MiddlewareUser::put_item_builder()
+                    .id("id0".to_owned())
+                    .name("bokuweb".to_owned())
+                    .build(),
+            ))
+            .run()
+            .await;
+        assert!(matches!(res.unwrap_err(), RaidenError::AccessDenied(_)));
+    }
+}
@@ -1,14 +1,28 @@
 mod batch_delete;
 mod batch_get;
+mod binary;
+mod capacity;
 mod condition;
+mod conversion_error;
 mod delete;
+mod document;
+mod empty_value;
+mod error_context;
+mod expr;
 mod filter_expression;
+mod format;
 mod get;
 mod key_condition;
+mod middleware;
+mod number;
 mod put;
 mod query;
 mod rename;
 mod rename_all;
+mod retry;
 mod scan;
+mod serde_attr;
 mod transact_write;
+mod transparent;
 mod update;
+mod with;
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::convert::TryFrom;
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Ledger")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Ledger {
+        #[raiden(partition_key)]
+        id: String,
+        amount: Number,
+        total: Number,
+        balance: Option<Number>,
+    }
+
+    fn number_attr(n: &str) -> AttributeValue {
+        AttributeValue {
+            n: Some(n.to_owned()),
+            ..AttributeValue::default()
+        }
+    }
+
+    #[test]
+    fn test_number_keeps_precision() {
+        let n = "12345678901234567890.123456789012345678";
+        let number = Number::from_attr(Some(number_attr(n))).unwrap();
+        assert_eq!(number.as_str(), n);
+        assert_eq!(number.into_attr().n, Some(n.to_owned()));
+    }
+
+    #[test]
+    fn test_number_rejects_invalid() {
+        for n in &[
+            "",
+            "-",
+            "1.2.3",
+            "1e",
+            "abc",
+            "123456789012345678901234567890123456789",
+        ] {
+            assert!(n.parse::<Number>().is_err(), "{}", n);
+        }
+        for n in &[
+            "0",
+            "-1.5",
+            ".5",
+            "1.",
+            "1E+10",
+            "1e-130",
+            "100000000000000000000000000000000000000000",
+        ] {
+            assert!(n.parse::<Number>().is_ok(), "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_128_bit_integers() {
+        let min = i128::MIN / 10;
+        let number = Number::from_attr(Some(number_attr(&min.to_string()))).unwrap();
+        assert_eq!(number.parse::<i128>().unwrap(), min);
+        let max = u128::MAX / 10;
+        assert_eq!(Number::try_from(max).unwrap().parse::<u128>().unwrap(), max);
+        // 39 significant digits.
+        assert_eq!(
+            Number::try_from(u128::MAX),
+            Err(InvalidNumber(u128::MAX.to_string()))
+        );
+        assert!(Number::try_from(i128::MIN).is_err());
+    }
+
+    #[test]
+    fn test_number_compares_by_value() {
+        let number = |n: &str| n.parse::<Number>().unwrap();
+        assert_eq!(number("1"), number("1.0"));
+        assert_eq!(number("100"), number("1E2"));
+        assert_eq!(number("0.05"), number("5e-2"));
+        assert_eq!(number("0"), number("-0.0"));
+        assert_ne!(number("1"), number("-1"));
+        assert_ne!(number("1"), number("10"));
+        let set: std::collections::HashSet<Number> = vec![number("1"), number("1.0"), number("01")]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(number("1.0").as_str(), "1.0");
+    }
+
+    #[test]
+    fn test_parse_error_is_returned() {
+        let error = usize::from_attr(Some(number_attr("1.5"))).unwrap_err();
+        assert_eq!(error.kind(), &ConversionErrorKind::ParseInt);
+        assert_eq!(error.expected(), Some(&AttributeType::N));
+        assert!(std::error::Error::source(&error).is_some());
+        assert_eq!(
+            f64::from_attr(Some(number_attr("abc"))).unwrap_err().kind(),
+            &ConversionErrorKind::ParseNumber
+        );
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_decimal() {
+        use rust_decimal::Decimal;
+        use std::convert::TryFrom;
+
+        let decimal: Decimal = "1234567890.0123456789".parse().unwrap();
+        let attr = decimal.into_attr();
+        assert_eq!(attr.n, Some("1234567890.0123456789".to_owned()));
+        assert_eq!(Decimal::from_attr(Some(attr)).unwrap(), decimal);
+        assert_eq!(
+            Decimal::from_attr(Some(number_attr("1.5E+2"))).unwrap(),
+            Decimal::new(150, 0)
+        );
+        assert_eq!(Decimal::try_from(Number::from(decimal)).unwrap(), decimal);
+    }
+}
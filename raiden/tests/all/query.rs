@@ -534,4 +534,61 @@ mod tests {
         }
         rt.block_on(example());
     }
+
+    // Keeps the capacity DynamoDB reported for every page of a query.
+    #[derive(Clone, Default)]
+    struct PageCapacities(std::sync::Arc<std::sync::Mutex<Vec<f64>>>);
+
+    #[async_trait]
+    impl Layer for PageCapacities {
+        async fn call(&self, request: Request, next: Next<'_>) -> Result<Response, RaidenError> {
+            let res = next.run(request).await?;
+            if let Response::Query(output) = &res {
+                let units = output
+                    .consumed_capacity
+                    .as_ref()
+                    .and_then(|capacity| capacity.capacity_units)
+                    .unwrap_or(0.0);
+                self.0.lock().unwrap().push(units);
+            }
+            Ok(res)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_query_aggregates_consumed_capacity_across_pages() {
+        let accumulator = CapacityAccumulator::new();
+        let pages = PageCapacities::default();
+        let client = QueryLargeDataTest::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+        .layer(pages.clone())
+        .with_capacity_accumulator(accumulator.clone());
+        let cond = QueryLargeDataTest::key_condition(QueryLargeDataTest::ref_id()).eq("ref");
+        // Items are 400KB each, so 1MB pages can't hold all 5 items.
+        let res = client
+            .query()
+            .index("testGSI")
+            .key_condition(cond)
+            .limit(5)
+            .return_consumed_capacity(ReturnConsumedCapacity::Indexes)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.items.len(), 5);
+
+        let pages = pages.0.lock().unwrap().clone();
+        assert!(pages.len() >= 2);
+        let consumed_capacity = res.consumed_capacity.unwrap();
+        assert!(consumed_capacity.global_secondary_indexes.is_some());
+        assert_eq!(
+            accumulator.total_capacity_units(),
+            pages.iter().sum::<f64>()
+        );
+        assert_eq!(
+            consumed_capacity.capacity_units.unwrap(),
+            pages.iter().sum::<f64>()
+        );
+    }
 }
@@ -0,0 +1,292 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    #[test]
+    fn test_default_strategy_retries_throttling() {
+        let strategy = DefaultRetryStrategy;
+        assert_eq!(
+            strategy.should_retry(&RaidenError::ThrottlingException("".to_owned())),
+            true
+        );
+        assert_eq!(
+            strategy.should_retry(&RaidenError::ProvisionedThroughputExceeded("".to_owned())),
+            true
+        );
+        assert_eq!(
+            strategy.should_retry(&RaidenError::ServiceUnavailable("".to_owned())),
+            true
+        );
+    }
+
+    #[test]
+    fn test_default_strategy_stops_on_client_errors() {
+        let strategy = DefaultRetryStrategy;
+        assert_eq!(
+            strategy.should_retry(&RaidenError::AccessDenied("".to_owned())),
+            false
+        );
+        assert_eq!(
+            strategy.should_retry(&RaidenError::ExpiredToken("".to_owned())),
+            false
+        );
+        assert_eq!(
+            strategy.should_retry(&RaidenError::ConditionalCheckFailed("".to_owned())),
+            false
+        );
+    }
+
+    #[test]
+    fn test_policy_backoff_stops_after_times() {
+        let policy = Policy::Exponential(2, std::time::Duration::from_millis(10));
+        assert!(policy.backoff(0).unwrap() <= std::time::Duration::from_millis(10));
+        assert!(policy.backoff(1).unwrap() <= std::time::Duration::from_millis(20));
+        assert_eq!(policy.backoff(2), None);
+        assert_eq!(Policy::None.backoff(0), None);
+    }
+
+    #[test]
+    fn test_policy_backoff_with_pause_and_limit() {
+        let policy = Policy::Pause(3, std::time::Duration::from_millis(50));
+        assert!(policy.backoff(2).unwrap() <= std::time::Duration::from_millis(50));
+        assert_eq!(policy.backoff(3), None);
+        assert!(Policy::Limit(1).backoff(0).unwrap() <= std::time::Duration::from_secs(1));
+        assert_eq!(Policy::Limit(1).backoff(1), None);
+    }
+
+    #[test]
+    fn test_retry_stops_when_budget_is_exhausted() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let budget = RetryBudget::new(10);
+        let condition = RetryCondition::with_strategy(Box::new(
+            DefaultRetryStrategy.with_budget(budget.clone()),
+        ));
+        let attempts = Attempts::default();
+        let res: Result<(), RaidenError> = rt.block_on(condition.retry(
+            Policy::Pause(5, std::time::Duration::from_millis(1)),
+            &attempts,
+            || async { Err(RaidenError::ThrottlingException("".to_owned())) },
+        ));
+        assert_eq!(res, Err(RaidenError::ThrottlingException("".to_owned())));
+        // The budget affords two retries out of five.
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(budget.available(), 0);
+    }
+
+    #[test]
+    fn test_retry_refills_budget_on_success() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let budget = RetryBudget::new(10).with_retry_cost(1);
+        let condition = RetryCondition::with_strategy(Box::new(
+            DefaultRetryStrategy.with_budget(budget.clone()),
+        ));
+        let attempts = Attempts::default();
+        let mut failures = 2;
+        let res = rt.block_on(condition.retry(
+            Policy::Pause(5, std::time::Duration::from_millis(1)),
+            &attempts,
+            || {
+                failures -= 1;
+                let failed = failures >= 0;
+                async move {
+                    if failed {
+                        Err(RaidenError::ServiceUnavailable("".to_owned()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        ));
+        assert_eq!(res, Ok(()));
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(budget.available(), 9);
+    }
+
+    #[test]
+    fn test_retry_does_not_charge_budget_past_policy() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let budget = RetryBudget::new(100);
+        let condition = RetryCondition::with_strategy(Box::new(
+            DefaultRetryStrategy.with_budget(budget.clone()),
+        ));
+        let attempts = Attempts::default();
+        let res: Result<(), RaidenError> = rt.block_on(condition.retry(
+            Policy::Pause(2, std::time::Duration::from_millis(1)),
+            &attempts,
+            || async { Err(RaidenError::ThrottlingException("".to_owned())) },
+        ));
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(budget.available(), 90);
+    }
+
+    #[test]
+    fn test_adaptive_strategy_enables_rate_limiting_on_throttle() {
+        let limiter = AdaptiveRateLimiter::new();
+        let strategy = DefaultRetryStrategy.adaptive(limiter.clone());
+        assert_eq!(
+            strategy.delay_before_send(),
+            std::time::Duration::from_millis(0)
+        );
+        strategy.on_response(Err(&RaidenError::ThrottlingException("".to_owned())));
+        assert!(limiter.rate().is_some());
+    }
+
+    #[test]
+    fn test_on_retry_is_called_with_attempt_error_and_delay() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let retries = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let mut condition = RetryCondition::new();
+        let recorded = retries.clone();
+        condition.on_retry(move |attempt, error, delay| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((attempt, error.to_string(), delay));
+        });
+        let attempts = Attempts::default();
+        let res: Result<(), RaidenError> = rt.block_on(condition.retry(
+            Policy::Pause(2, std::time::Duration::from_millis(1)),
+            &attempts,
+            || async { Err(RaidenError::ThrottlingException("slow down".to_owned())) },
+        ));
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 3);
+        let retries = retries.lock().unwrap();
+        assert_eq!(retries.iter().map(|r| r.0).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(retries[0].1.contains("slow down"));
+        assert!(retries
+            .iter()
+            .all(|r| r.2 <= std::time::Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_on_retry_is_not_called_for_non_retryable_errors() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let called = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = called.clone();
+        let mut condition = RetryCondition::with_strategy(Box::new(DefaultRetryStrategy));
+        condition.on_retry(move |_, _, _| flag.store(true, std::sync::atomic::Ordering::SeqCst));
+        let attempts = Attempts::default();
+        let res: Result<(), RaidenError> =
+            rt.block_on(condition.retry(Policy::default(), &attempts, || async {
+                Err(RaidenError::AccessDenied("".to_owned()))
+            }));
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 1);
+        assert!(!called.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_attempts_are_counted_per_request() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let condition = RetryCondition::default();
+        let attempts = Attempts::default();
+        // The first page is retried twice, the second one succeeds at once.
+        let mut failures = 2;
+        for _ in 0..2 {
+            let res = rt.block_on(condition.retry(
+                Policy::Pause(5, std::time::Duration::from_millis(1)),
+                &attempts,
+                || {
+                    failures -= 1;
+                    let failed = failures >= 0;
+                    async move {
+                        if failed {
+                            Err(RaidenError::ThrottlingException("".to_owned()))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                },
+            ));
+            assert_eq!(res, Ok(()));
+        }
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_retries_are_summed_over_requests() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let condition = RetryCondition::default();
+        let attempts = Attempts::default();
+        // Each of three pages fails once before succeeding.
+        for _ in 0..3 {
+            let mut failed = false;
+            let res = rt.block_on(condition.retry(
+                Policy::Pause(5, std::time::Duration::from_millis(1)),
+                &attempts,
+                || {
+                    let fail = !failed;
+                    failed = true;
+                    async move {
+                        if fail {
+                            Err(RaidenError::ThrottlingException("".to_owned()))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                },
+            ));
+            assert_eq!(res, Ok(()));
+        }
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(attempts.retries(), 3);
+
+        // A request which isn't retried adds nothing.
+        let res: Result<(), RaidenError> =
+            rt.block_on(condition.retry(Policy::default(), &attempts, || async {
+                Err(RaidenError::AccessDenied("".to_owned()))
+            }));
+        assert!(res.is_err());
+        assert_eq!(attempts.get(), 1);
+        assert_eq!(attempts.retries(), 3);
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[allow(dead_code)]
+    pub struct RetryUser {
+        #[raiden(partition_key)]
+        id: String,
+    }
+
+    #[test]
+    fn test_with_retries_keeps_on_retry_hooks() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let retries = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = retries.clone();
+        let client = RetryUser::client(Region::UsEast1)
+            .on_retry(move |_, _, _| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            })
+            .with_retries(Box::new(DefaultRetryStrategy));
+        let builder = client.get("id0");
+        let attempts = Attempts::default();
+        let res: Result<(), RaidenError> = rt.block_on(builder.condition.retry(
+            Policy::Pause(2, std::time::Duration::from_millis(1)),
+            &attempts,
+            || async { Err(RaidenError::ThrottlingException("".to_owned())) },
+        ));
+        assert!(res.is_err());
+        assert_eq!(retries.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_builder_overrides_client_policy() {
+        let client = RetryUser::client(Region::UsEast1).on_retry(|_, _, _| {});
+        assert_eq!(client.get("id0").policy, Policy::default());
+        assert_eq!(client.get("id0").retry(Policy::None).policy, Policy::None);
+        assert_eq!(
+            client.query().retry(Policy::Limit(1)).policy,
+            Policy::Limit(1)
+        );
+        assert_eq!(
+            client.delete("id0").retry(Policy::Limit(2)).policy,
+            Policy::Limit(2)
+        );
+    }
+}
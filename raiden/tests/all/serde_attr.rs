@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Address {
+        city: String,
+        zip: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Plan {
+        Free,
+        Paid { seats: u32 },
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct User {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(serde)]
+        address: Address,
+        #[raiden(serde)]
+        plan: Option<Plan>,
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "board")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Board {
+        #[raiden(partition_key)]
+        id: String,
+        // JSON maps need string keys, so this doesn't serialize unless empty.
+        #[raiden(serde)]
+        cells: std::collections::HashMap<(u32, u32), String>,
+    }
+
+    fn cells() -> std::collections::HashMap<(u32, u32), String> {
+        vec![((0, 0), "x".to_owned())].into_iter().collect()
+    }
+
+    #[test]
+    fn test_serde_field_is_stored_as_map() {
+        let client = User::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        });
+        let user = UserPutItemInput {
+            id: "id0".to_owned(),
+            address: Address {
+                city: "Tokyo".to_owned(),
+                zip: None,
+            },
+            plan: Some(Plan::Paid { seats: 3 }),
+        };
+        let item = client.put(user).input.item;
+        let address = item["address"].m.as_ref().unwrap();
+        assert_eq!(address["city"].s, Some("Tokyo".to_owned()));
+        assert_eq!(address["zip"].null, Some(true));
+        let plan = item["plan"].m.as_ref().unwrap();
+        assert_eq!(
+            plan["paid"].m.as_ref().unwrap()["seats"].n,
+            Some("3".to_owned())
+        );
+
+        let plan: Option<Plan> = serde_attr::from_attr(Some(item["plan"].clone())).unwrap();
+        assert_eq!(plan, Some(Plan::Paid { seats: 3 }));
+    }
+
+    #[test]
+    fn test_to_item_and_from_item() {
+        let address = Address {
+            city: "Tokyo".to_owned(),
+            zip: Some("100-0001".to_owned()),
+        };
+        let item = to_item(&address).unwrap();
+        assert_eq!(item["zip"].s, Some("100-0001".to_owned()));
+        assert_eq!(from_item::<Address>(item).unwrap(), address);
+    }
+
+    #[test]
+    fn test_serde_errors() {
+        assert_eq!(to_item(&1).unwrap_err().kind(), &ConversionErrorKind::Serde);
+        let item = to_item(&serde_json::json!({ "city": 1 })).unwrap();
+        let error = from_item::<Address>(item).unwrap_err();
+        assert_eq!(error.kind(), &ConversionErrorKind::Serde);
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_serde_accessors() {
+        let (_, _, values) = User::cond().plan().eq(Plan::Free).build();
+        assert_eq!(values[":value0"].s, Some("free".to_owned()));
+        let (_, _, values) = User::filter()
+            .address()
+            .eq(Address {
+                city: "Tokyo".to_owned(),
+                zip: None,
+            })
+            .build();
+        assert_eq!(
+            values[":value0"].m.as_ref().unwrap()["city"].s,
+            Some("Tokyo".to_owned())
+        );
+
+        let error = Board::cond()
+            .cells()
+            .eq(cells())
+            .and(Board::cond().id().exists())
+            .try_build_with(&mut Placeholders::new())
+            .unwrap_err();
+        assert_eq!(error.attr_name, "cells");
+        assert_eq!(error.source.kind(), &ConversionErrorKind::Serde);
+    }
+
+    #[tokio::test]
+    async fn test_serde_error_is_returned_by_run() {
+        let client = Board::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        });
+        let board = BoardPutItemInput {
+            id: "id0".to_owned(),
+            cells: cells(),
+        };
+        let error = client.put(board).run().await.unwrap_err();
+        assert!(matches!(
+            error,
+            RaidenError::AttributeConvertError { attr_name, .. } if attr_name == "cells"
+        ));
+
+        let error = client
+            .query()
+            .key_condition(Board::keys().id().eq("id0".to_owned()))
+            .filter(Board::filter().cells().eq(cells()))
+            .run()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            RaidenError::AttributeConvertError { attr_name, .. } if attr_name == "cells"
+        ));
+    }
+}
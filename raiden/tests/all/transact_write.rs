@@ -264,16 +264,82 @@ mod tests {
                 .run()
                 .await;
             assert_eq!(res.is_err(), true,);
+            let err = res.unwrap_err();
+            let mut key = std::collections::HashMap::new();
+            key.insert("id".to_owned(), "id1".into_attr());
             assert_eq!(
-                res.unwrap_err(),
-                RaidenError::TransactionCanceled {
-                    reasons: RaidenTransactionCancellationReasons(vec![
+                err.failed_items(),
+                &[RaidenTransactionFailedItem {
+                    index: 1,
+                    table_name: Some("TxConditionalCheckTestData1".to_owned()),
+                    key: Some(key),
+                    reason: RaidenTransactionCancellationReason::ConditionalCheckFailed,
+                    message: Some("The conditional request failed".to_owned()),
+                    item: None,
+                }]
+            );
+            assert!(matches!(
+                err,
+                RaidenError::TransactionCanceled { reasons, .. }
+                    if reasons == RaidenTransactionCancellationReasons(vec![
                         None,
                         Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
-                    ]),
-                }
-            );
+                    ])
+            ));
+        }
+        rt.block_on(example());
+    }
+
+    #[test]
+    fn should_return_old_item_of_failed_condition_check() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        async fn example() {
+            let tx = ::raiden::WriteTx::new(Region::Custom {
+                endpoint: "http://localhost:8000".into(),
+                name: "ap-northeast-1".into(),
+            });
+            let input = TxConditionalCheckTestData0::put_item_builder()
+                .id("testId2".to_owned())
+                .name("bokuweb".to_owned())
+                .build();
+            let cond = TxConditionalCheckTestData1::condition()
+                .attr_not_exists(TxConditionalCheckTestData1::id());
+
+            let err = tx
+                .put(TxConditionalCheckTestData0::put(input))
+                .condition_check(
+                    TxConditionalCheckTestData1::condition_check("id1").condition(cond),
+                )
+                .return_all_old_on_condition_check_failure()
+                .run()
+                .await
+                .unwrap_err();
+            let mut item = std::collections::HashMap::new();
+            item.insert("id".to_owned(), "id1".into_attr());
+            item.insert("name".to_owned(), "world".into_attr());
+            assert_eq!(err.failed_items().len(), 1);
+            assert_eq!(err.failed_items()[0].index, 1);
+            assert_eq!(err.failed_items()[0].item, Some(item));
         }
         rt.block_on(example());
     }
+
+    #[tokio::test]
+    async fn test_transact_write_returns_consumed_capacity() {
+        let input = User::put_item_builder()
+            .id("testId3".to_owned())
+            .name("bokuweb".to_owned())
+            .build();
+        let res = ::raiden::WriteTx::new(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
+        .put(User::put(input))
+        .run()
+        .await
+        .unwrap();
+        let consumed_capacity = res.consumed_capacity.unwrap();
+        assert_eq!(consumed_capacity[0].table_name, Some("user".to_owned()));
+    }
 }
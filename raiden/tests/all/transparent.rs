@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::collections::HashSet;
+
+    #[derive(RaidenAttribute, Debug, Clone, PartialEq, Eq, Hash)]
+    #[raiden(transparent)]
+    pub struct UserId(String);
+
+    #[derive(RaidenAttribute, Debug, Clone, Copy, PartialEq)]
+    #[raiden(transparent)]
+    pub struct Revision(u64);
+
+    #[derive(RaidenAttribute, Debug, Clone, PartialEq)]
+    #[raiden(transparent)]
+    pub struct Tagged<T>(T);
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Account")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Account {
+        #[raiden(partition_key)]
+        id: UserId,
+        #[raiden(sort_key)]
+        revision: Revision,
+        friends: HashSet<UserId>,
+    }
+
+    fn client() -> AccountClient {
+        Account::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[test]
+    fn test_transparent_round_trip() {
+        let attr = UserId("id0".to_owned()).into_attr();
+        assert_eq!(attr, "id0".into_attr());
+        assert_eq!(UserId::from_attr(Some(attr)), Ok(UserId("id0".to_owned())));
+        assert_eq!(Revision(3).into_attr(), 3.into_attr());
+        assert_eq!(Revision::from_attr(Some(3.into_attr())), Ok(Revision(3)));
+        assert_eq!(Tagged(3u64).into_attr(), 3.into_attr());
+        assert_eq!(
+            Tagged::<String>::from_attr(Some("id0".into_attr())),
+            Ok(Tagged("id0".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_transparent_keys() {
+        let input = client().get("id0", 1).input;
+        assert_eq!(input.key["id"], "id0".into_attr());
+        assert_eq!(input.key["revision"], 1.into_attr());
+
+        let input = client().get(UserId("id1".to_owned()), Revision(2)).input;
+        assert_eq!(input.key["id"], "id1".into_attr());
+
+        let (expression, _, values) = Account::keys()
+            .id()
+            .eq(UserId("id0".to_owned()))
+            .and(Account::keys().revision().gt(Revision(1)))
+            .build();
+        assert_eq!(expression, "#id = :value0 AND (#revision > :value1)");
+        assert_eq!(values[":value1"], 1.into_attr());
+    }
+
+    #[test]
+    fn test_transparent_string_set() {
+        let friends: HashSet<UserId> = vec![UserId("id1".to_owned())].into_iter().collect();
+        let item = AccountPutItemInput {
+            id: UserId("id0".to_owned()),
+            revision: Revision(1),
+            friends: friends.clone(),
+        };
+        let item = client().put(item).input.item;
+        assert_eq!(item["friends"].ss, Some(vec!["id1".to_owned()]));
+        assert_eq!(
+            HashSet::<UserId>::from_attr(Some(item["friends"].clone())),
+            Ok(friends)
+        );
+
+        let cond = Account::cond().id().eq(UserId("id0".to_owned()));
+        let (expression, _, _) = cond.build();
+        assert_eq!(expression, "#id = :value0");
+    }
+}
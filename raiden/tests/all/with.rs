@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    mod ipv4 {
+        use raiden::*;
+        use std::net::Ipv4Addr;
+
+        pub fn into_attr(value: Ipv4Addr) -> AttributeValue {
+            value.to_string().into_attr()
+        }
+
+        pub fn from_attr(value: Option<AttributeValue>) -> Result<Ipv4Addr, ConversionError> {
+            let s = String::from_attr(value)?;
+            s.parse().map_err(|e| {
+                ConversionError::new(ConversionErrorKind::TypeMismatch)
+                    .with_expected(AttributeType::S)
+                    .with_cause(e)
+            })
+        }
+    }
+
+    fn duration_into_attr(value: Duration) -> AttributeValue {
+        (value.as_millis() as u64).into_attr()
+    }
+
+    fn duration_from_attr(value: Option<AttributeValue>) -> Result<Duration, ConversionError> {
+        u64::from_attr(value).map(Duration::from_millis)
+    }
+
+    #[derive(Raiden)]
+    #[raiden(table_name = "Host")]
+    #[derive(Debug, Clone)]
+    #[allow(dead_code)]
+    pub struct Host {
+        #[raiden(partition_key)]
+        #[raiden(with = "ipv4")]
+        address: Ipv4Addr,
+        #[raiden(into_with = "duration_into_attr")]
+        #[raiden(from_with = "duration_from_attr")]
+        timeout: Duration,
+        name: String,
+    }
+
+    fn client() -> HostClient {
+        Host::client(Region::Custom {
+            endpoint: "http://localhost:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[test]
+    fn test_put_with_converters() {
+        let host = HostPutItemInput {
+            address: Ipv4Addr::new(127, 0, 0, 1),
+            timeout: Duration::from_secs(3),
+            name: "localhost".to_owned(),
+        };
+        let item = client().put(host).input.item;
+        assert_eq!(item["address"].s, Some("127.0.0.1".to_owned()));
+        assert_eq!(item["timeout"].n, Some("3000".to_owned()));
+        assert_eq!(
+            ipv4::from_attr(Some(item["address"].clone())).unwrap(),
+            Ipv4Addr::new(127, 0, 0, 1)
+        );
+        assert_eq!(
+            duration_from_attr(Some(item["timeout"].clone())).unwrap(),
+            Duration::from_secs(3)
+        );
+    }
+
+    #[test]
+    fn test_key_with_converter() {
+        let input = client().get(Ipv4Addr::new(10, 0, 0, 1)).input;
+        assert_eq!(input.key["address"].s, Some("10.0.0.1".to_owned()));
+    }
+}
@@ -0,0 +1,19 @@
+import type { CreateAndPut } from "../dynamo_util.ts";
+
+export const defaultFnTestData0: CreateAndPut = {
+  table: {
+    TableName: "DefaultFnTestData0",
+    KeySchema: [{ AttributeName: "id", KeyType: "HASH" }],
+    AttributeDefinitions: [{ AttributeName: "id", AttributeType: "S" }],
+    ProvisionedThroughput: { ReadCapacityUnits: 5, WriteCapacityUnits: 5 },
+  },
+  items: [
+    { id: { S: "id0" } },
+    {
+      id: { S: "id1" },
+      name: { S: "bokuweb" },
+      count: { S: "ten" },
+      label: { N: "1" },
+    },
+  ],
+};
@@ -37,6 +37,7 @@ import { updateRemoveTestData0 } from "./fixtures/update_remove_test_data_0.ts";
 import { updateWithContainsInSetCondition } from "./fixtures/update_with_contains_in_set_condition.ts";
 import { queryLargeDataTest } from "./fixtures/query_large_data_test.ts";
 import { scanLargeDataTest } from "./fixtures/scan_large_data_test.ts";
+import { defaultFnTestData0 } from "./fixtures/default_fn_test_data_0.ts";
 
 const client = new DynamoDBClient({
   region: "ap-northeast-1",
@@ -82,6 +83,7 @@ const data = [
   queryLargeDataTest,
   scanLargeDataTest,
   useDefaultForNull,
+  defaultFnTestData0,
 ];
 
 // NOTE: Running these operations concurrently with `Promise.all` would lead to running out of write buffer.